@@ -0,0 +1,98 @@
+//! Generates the module list for the in-process solution registry (see `src/template/registry.rs`).
+//! Every `src/bin/DD.rs` and `src/bin/YYYY-DD.rs` file is compiled into the library as a module
+//! so `all` can call it directly. Files named `DD-<variant>.rs` or `YYYY-DD-<variant>.rs` are
+//! registered as named variants of that day.
+//! The library compiles copies of the files without their `tests` module, so the tests of a day
+//! only run once, with its binary. Lints are reported for the binaries as well, so the copies
+//! allow them.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter_map(|name| name.strip_suffix(".rs").map(str::to_string))
+                .filter(|stem| split_variant(stem).is_some())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).join("days");
+    fs::create_dir_all(&out_dir).unwrap();
+
+    let mut generated = String::new();
+
+    for day in &days {
+        let source_path = bin_dir.join(format!("{day}.rs"));
+        println!("cargo:rerun-if-changed={}", source_path.display());
+
+        let source = fs::read_to_string(&source_path).unwrap();
+        let path = out_dir.join(format!("{day}.rs"));
+        fs::write(&path, without_tests(&source)).unwrap();
+
+        generated.push_str(&format!(
+            "#[allow(dead_code, clippy::all, clippy::pedantic)]\n#[path = {:?}]\nmod {};\n\n",
+            path.display().to_string(),
+            module_name(day)
+        ));
+    }
+
+    generated.push_str("pub(super) const SOLUTIONS: &[super::Solution] = &[\n");
+    for day in &days {
+        match split_variant(day) {
+            Some((_, Some(variant))) => generated.push_str(&format!(
+                "    super::Solution {{ variant: Some({variant:?}), ..{}::SOLUTION }},\n",
+                module_name(day)
+            )),
+            _ => generated.push_str(&format!("    {}::SOLUTION,\n", module_name(day))),
+        }
+    }
+    generated.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, generated).unwrap();
+}
+
+/// Matches `DD` (default year) and `YYYY-DD` file stems.
+fn is_solution(stem: &str) -> bool {
+    let is_digits = |x: &str, len: usize| x.len() == len && x.chars().all(|c| c.is_ascii_digit());
+
+    match stem.split_once('-') {
+        Some((year, day)) => is_digits(year, 4) && is_digits(day, 2),
+        None => is_digits(stem, 2),
+    }
+}
+
+/// Splits a file stem into the solution it belongs to and the name of its variant, e.g.
+/// `10-softwaregore` into `10` and `softwaregore`. Returns [`None`] for files that are not solutions.
+fn split_variant(stem: &str) -> Option<(&str, Option<&str>)> {
+    if is_solution(stem) {
+        return Some((stem, None));
+    }
+
+    let (solution, variant) = stem.rsplit_once('-')?;
+    let is_name = variant.starts_with(|c: char| c.is_ascii_lowercase())
+        && variant
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    (is_solution(solution) && is_name).then_some((solution, Some(variant)))
+}
+
+/// Disables the `#[cfg(test)] mod tests` module of a solution.
+fn without_tests(source: &str) -> String {
+    source.replace("#[cfg(test)]\nmod tests", "#[cfg(any())]\nmod tests")
+}
+
+fn module_name(stem: &str) -> String {
+    format!("day_{}", stem.replace('-', "_"))
+}
@@ -1,4 +1,4 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, shape = Lines, pattern = r"^[a-z0-9]+$");
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
@@ -1,4 +1,8 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(
+    2,
+    shape = Lines,
+    pattern = r"^Game \d+: \d+ (red|green|blue)((, |; )\d+ (red|green|blue))*$"
+);
 
 use regex::Regex;
 
@@ -1,6 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, shape = Grid, pattern = r"^[0-9.*#+$/@=%&-]+$");
 
 enum Item {
     Empty,
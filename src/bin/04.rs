@@ -1,6 +1,10 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(
+    4,
+    shape = Lines,
+    pattern = r"^Card +\d+:( +\d+)+ \|( +\d+)+$"
+);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let cards: Vec<u32> = input
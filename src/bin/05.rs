@@ -1,6 +1,10 @@
 use std::ops::{Add, Range, Sub};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(
+    5,
+    shape = Blocks,
+    pattern = r"^(seeds:( \d+)+|[a-z]+-to-[a-z]+ map:|\d+ \d+ \d+)$"
+);
 
 // Handle unsigned-safe shifting from start to dest for any unsigned int type
 fn unsigned_safe_shift<T>(to_change: T, start: T, dest: T) -> T
@@ -26,22 +30,28 @@ where
     T: PartialOrd<T> + Copy,
 {
     fn subdiv(&self, other: &Self) -> (Option<Self>, Option<Vec<Self>>) {
-        if self.start >= other.start && self.end < other.end {
+        if self.start >= other.start && self.end <= other.end {
             // self range is inside other range
             (Some(self.clone()), None)
         } else if other.start >= self.start && other.end < self.end {
             // self range surrounds other range
             let overlapping_range = Some(other.clone());
-            let non_overlapping = Some(vec![
-                Range {
-                    start: self.start,
-                    end: other.start,
-                },
-                Range {
-                    start: other.end,
-                    end: self.end,
-                },
-            ]);
+            let non_overlapping = Some(
+                [
+                    Range {
+                        start: self.start,
+                        end: other.start,
+                    },
+                    Range {
+                        start: other.end,
+                        end: self.end,
+                    },
+                ]
+                .into_iter()
+                // both ranges may start at the same point
+                .filter(|range| !range.is_empty())
+                .collect(),
+            );
             (overlapping_range, non_overlapping)
         } else if self.start >= other.start && self.start < other.end {
             // self range partial overalp  with other range on left
@@ -180,8 +190,8 @@ pub fn part_two(input: &str) -> Option<u64> {
         let mut returns = Vec::new();
         let mut searching = vec![x];
         for (dest, transform_range) in transformer.iter() {
-            let search_clone = searching.clone();
-            for check_range in search_clone.iter() {
+            let mut remaining = Vec::new();
+            for check_range in searching.iter() {
                 let (overlapping, non_overlapping) = check_range.subdiv(transform_range);
                 if let Some(overlapping) = overlapping {
                     returns.push(Range {
@@ -190,11 +200,10 @@ pub fn part_two(input: &str) -> Option<u64> {
                     });
                 }
                 if let Some(non_overlapping) = non_overlapping {
-                    searching = non_overlapping;
-                } else {
-                    searching = Vec::new();
+                    remaining.extend(non_overlapping);
                 }
             }
+            searching = remaining;
             if searching.is_empty() {
                 break;
             }
@@ -268,6 +277,25 @@ mod tests {
         assert_eq!(rest.1, None);
     }
 
+    #[test]
+    fn test_subdiv_range_edges() {
+        let range1 = Range { start: 5, end: 10 };
+
+        // self ends where other ends
+        let rest = range1.subdiv(&Range { start: 2, end: 10 });
+        assert_eq!(rest, (Some(Range { start: 5, end: 10 }), None));
+
+        // self starts where other starts
+        let rest = range1.subdiv(&Range { start: 5, end: 7 });
+        assert_eq!(
+            rest,
+            (
+                Some(Range { start: 5, end: 7 }),
+                Some(vec![Range { start: 7, end: 10 }])
+            )
+        );
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -279,4 +307,27 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    fn test_part_two_range_ending_with_map() {
+        let result = part_two("seeds: 5 5\n\nseed-to-soil map:\n50 2 8\n");
+        assert_eq!(result, Some(53));
+    }
+
+    #[test]
+    fn test_part_two_range_split_by_several_maps() {
+        let result = part_two("seeds: 0 10\n\nseed-to-soil map:\n100 4 2\n200 0 2\n");
+        assert_eq!(result, Some(2));
+    }
+
+    #[cfg(feature = "test_lib")]
+    #[test]
+    fn test_against_brute_force() {
+        use advent_of_code::template::testing::{check, day05};
+
+        check(1000, day05::generate, |input| {
+            assert_eq!(part_one(input), Some(day05::part_one(input)));
+            assert_eq!(part_two(input), Some(day05::part_two(input)));
+        });
+    }
 }
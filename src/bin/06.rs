@@ -1,4 +1,8 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(
+    6,
+    shape = Lines,
+    pattern = r"^(Time|Distance): +\d+( +\d+)*$"
+);
 
 fn get_lower_race_quadratic(time: u64, record: u64) -> u64 {
     let delta = time.pow(2) - 4 * record;
@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, shape = Lines, pattern = r"^[AKQJT2-9]{5} \d+$");
 
 #[derive(Debug)]
 struct Hand {
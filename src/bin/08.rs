@@ -2,7 +2,11 @@ use std::collections::HashMap;
 
 use regex::Regex;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(
+    8,
+    shape = Blocks,
+    pattern = r"^([LR]+|\w{3} = \(\w{3}, \w{3}\))$"
+);
 
 fn build_map(lines: Vec<&str>) -> HashMap<String, (String, String)> {
     let re = Regex::new(r"(\w{3}) = \((\w{3}), (\w{3})\)").unwrap();
@@ -116,26 +120,15 @@ pub fn part_two(input: &str) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
-    use std::{env, fs};
-
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-
-    fn read_file(folder: &str, file: &str) -> String {
-        let cwd = env::current_dir().unwrap();
-        let filepath = cwd.join("data").join(folder).join(file);
-        let f = fs::read_to_string(filepath);
-        f.expect("could not open input file")
+        advent_of_code::template::examples::check_part(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&read_file("examples", "08_2.txt"));
-        assert_eq!(result, Some(6));
+        advent_of_code::template::examples::check_part(DAY, 2, part_two);
     }
 }
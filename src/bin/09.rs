@@ -1,4 +1,4 @@
-advent_of_code::solution!(9);
+advent_of_code::solution!(9, shape = Lines, pattern = r"^-?\d+( -?\d+)+$");
 
 fn parse_nums(line: &str) -> Vec<i64> {
     line.split_whitespace()
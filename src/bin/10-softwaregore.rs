@@ -6,7 +6,7 @@ use std::{
     collections::{HashSet, VecDeque},
 };
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, shape = Grid, pattern = r"^[|\-LJ7FSIO.]+$");
 
 #[derive(Clone, Copy, Debug)]
 enum Direction {
@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, shape = Grid, pattern = r"^[|\-LJ7FSIO.]+$");
 
 #[derive(Clone, Copy, Debug)]
 enum Direction {
@@ -232,26 +232,15 @@ pub fn part_two(input: &str) -> Option<u64> {
 
 #[cfg(test)]
 mod tests {
-    use std::{env, fs};
-
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
-    }
-
-    fn read_file(folder: &str, file: &str) -> String {
-        let cwd = env::current_dir().unwrap();
-        let filepath = cwd.join("data").join(folder).join(file);
-        let f = fs::read_to_string(filepath);
-        f.expect("could not open input file")
+        advent_of_code::template::examples::check_part(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&read_file("examples", "10_2.txt"));
-        assert_eq!(result, Some(10));
+        advent_of_code::template::examples::check_part(DAY, 2, part_two);
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, shape = Grid, pattern = r"^[.#]+$");
 
 fn dist_with_expansion(
     g1: (usize, usize),
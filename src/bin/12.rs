@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, shape = Lines, pattern = r"^[.#?]+ \d+(,\d+)*$");
 
 fn parse_record(line: &str) -> (String, Vec<usize>) {
     let items: Vec<&str> = line.split_whitespace().collect();
@@ -1,6 +1,6 @@
 use std::cmp;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(13, shape = Blocks, pattern = r"^[.#]+$");
 
 fn do_vecs_match<T: PartialEq>(a: &[T], b: &[T]) -> bool {
     let matching = a.iter().zip(b.iter()).filter(|&(a, b)| a == b).count();
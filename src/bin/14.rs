@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, shape = Grid, pattern = r"^[.#O]+$");
 
 fn rotate_north(map: Vec<Vec<char>>) -> Vec<Vec<char>> {
     let mut map = map;
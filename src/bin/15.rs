@@ -1,4 +1,8 @@
-advent_of_code::solution!(15);
+advent_of_code::solution!(
+    15,
+    shape = Lines,
+    pattern = r"^[a-z]+(=\d|-)(,[a-z]+(=\d|-))*$"
+);
 
 fn hash_it(s: &str) -> usize {
     s.as_bytes()
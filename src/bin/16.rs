@@ -3,7 +3,7 @@ use std::{
     collections::{HashSet, VecDeque},
 };
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, shape = Grid, pattern = r"^[.|\-\\/]+$");
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 enum Direction {
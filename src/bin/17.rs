@@ -1,4 +1,4 @@
-advent_of_code::solution!(17);
+advent_of_code::solution!(17, shape = Grid, pattern = r"^[1-9]+$");
 
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
@@ -2,7 +2,11 @@ use std::cmp;
 
 use regex::Regex;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(
+    18,
+    shape = Lines,
+    pattern = r"^[UDLR] \d+ \(#[0-9a-f]{6}\)$"
+);
 
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
 enum Direction {
@@ -3,9 +3,14 @@ use std::{
     ops::Range,
 };
 
+use advent_of_code::{ParseError, ParseResult};
 use regex::Regex;
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(
+    19,
+    shape = Blocks,
+    pattern = r"^([a-z]+\{([xmas][<>]\d+:[a-zAR]+,)*[a-zAR]+\}|\{x=\d+,m=\d+,a=\d+,s=\d+\})$"
+);
 
 #[derive(Debug, Clone, Copy)]
 enum Category {
@@ -22,7 +27,7 @@ impl Category {
             "m" => Self::M,
             "a" => Self::A,
             "s" => Self::S,
-            _ => panic!("Unexpected string for Category {}", s),
+            _ => unreachable!("rules only match the categories x, m, a and s"),
         }
     }
 }
@@ -52,30 +57,25 @@ enum Workflow {
 }
 
 impl Workflow {
-    fn from(s: &str) -> Self {
+    fn parse(input: &str, s: &str) -> ParseResult<Self> {
         if !s.contains(':') {
-            return Self::Nil(Result::from(s));
+            return Ok(Self::Nil(Result::from(s)));
         }
         let re = Regex::new(r"^([xmas])([><])(\d+):([a-zA-Z]+)$").unwrap();
-        let items = re
-            .captures(s)
-            .unwrap_or_else(|| panic!("Should find Op format {}", s));
+        let items = re.captures(s).ok_or_else(|| {
+            ParseError::new(
+                input,
+                s,
+                format!("expected a rule like `a<2006:qkq`, got {s:?}"),
+            )
+        })?;
+        let category = Category::from(&items[1]);
+        let num = items[3]
+            .parse::<usize>()
+            .map_err(|e| ParseError::new(input, &items[3], format!("invalid rating: {e}")))?;
         match &items[2] {
-            ">" => Self::Greater(
-                Category::from(&items[1]),
-                items[3]
-                    .parse::<usize>()
-                    .expect("Should be able to parse Op num"),
-                Result::from(&items[4]),
-            ),
-            "<" => Self::Less(
-                Category::from(&items[1]),
-                items[3]
-                    .parse::<usize>()
-                    .expect("Should be able to parse Op num"),
-                Result::from(&items[4]),
-            ),
-            _ => panic!("Should be comparison at second spot {}", s),
+            ">" => Ok(Self::Greater(category, num, Result::from(&items[4]))),
+            _ => Ok(Self::Less(category, num, Result::from(&items[4]))),
         }
     }
 
@@ -109,17 +109,26 @@ struct Part {
 }
 
 impl Part {
-    fn from(s: &str) -> Self {
+    fn parse(input: &str, s: &str) -> ParseResult<Self> {
         let re = Regex::new(r"^\{x=(\d+),m=(\d+),a=(\d+),s=(\d+)\}$").unwrap();
-        let splits = re
-            .captures(s)
-            .unwrap_or_else(|| panic!("Expected to parse Part {}", s));
-        Part {
-            x: splits[1].parse().unwrap(),
-            m: splits[2].parse().unwrap(),
-            a: splits[3].parse().unwrap(),
-            s: splits[4].parse().unwrap(),
-        }
+        let splits = re.captures(s).ok_or_else(|| {
+            ParseError::new(
+                input,
+                s,
+                format!("expected a part like `{{x=787,m=2655,a=1222,s=2876}}`, got {s:?}"),
+            )
+        })?;
+        let rating = |i: usize| {
+            splits[i]
+                .parse()
+                .map_err(|e| ParseError::new(input, &splits[i], format!("invalid rating: {e}")))
+        };
+        Ok(Part {
+            x: rating(1)?,
+            m: rating(2)?,
+            a: rating(3)?,
+            s: rating(4)?,
+        })
     }
 
     fn sum(&self) -> usize {
@@ -176,27 +185,32 @@ impl PartRanges {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+fn parse_workflows(input: &str) -> ParseResult<HashMap<String, Vec<Workflow>>> {
     let re = Regex::new(r"^([a-zA-Z]+)\{(.+)\}$").unwrap();
-    let (workflows_map, parts, _): (HashMap<String, Vec<Workflow>>, Vec<Part>, bool) = input
+    input
         .lines()
-        .fold((HashMap::new(), Vec::new(), false), |mut acc, line| {
-            if line.is_empty() {
-                acc.2 = true;
-                return acc;
-            }
-            if !acc.2 {
-                let splits = re
-                    .captures(line)
-                    .unwrap_or_else(|| panic!("Should be able to split workflow {}", line));
-                let key = &splits[1];
-                let workflows = splits[2].split(',').map(Workflow::from).collect();
-                acc.0.insert(key.to_owned(), workflows);
-            } else {
-                acc.1.push(Part::from(line));
-            }
-            acc
-        });
+        .take_while(|&line| !line.is_empty())
+        .map(|line| {
+            let splits = re.captures(line).ok_or_else(|| {
+                ParseError::new(input, line, "expected a workflow like `px{a<2006:qkq,rfg}`")
+            })?;
+            let workflows = splits[2]
+                .split(',')
+                .map(|s| Workflow::parse(input, s))
+                .collect::<ParseResult<_>>()?;
+            Ok((splits[1].to_owned(), workflows))
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> ParseResult<u64> {
+    let workflows_map = parse_workflows(input)?;
+    let parts = input
+        .lines()
+        .skip_while(|&line| !line.is_empty())
+        .skip(1)
+        .map(|line| Part::parse(input, line))
+        .collect::<ParseResult<Vec<_>>>()?;
     let mut accepted = Vec::new();
     for part in parts.iter() {
         let mut curr = String::from("in");
@@ -220,23 +234,11 @@ pub fn part_one(input: &str) -> Option<u64> {
             accepted.push(part);
         }
     }
-    Some(accepted.iter().map(|p| p.sum()).sum::<usize>() as u64)
+    Ok(accepted.iter().map(|p| p.sum()).sum::<usize>() as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let re = Regex::new(r"^([a-zA-Z]+)\{(.+)\}$").unwrap();
-    let workflows_map: HashMap<String, Vec<Workflow>> = input
-        .lines()
-        .take_while(|&line| !line.is_empty())
-        .fold(HashMap::new(), |mut acc, line| {
-            let splits = re
-                .captures(line)
-                .unwrap_or_else(|| panic!("Should be able to split workflow {}", line));
-            let key = &splits[1];
-            let workflows = splits[2].split(',').map(Workflow::from).collect();
-            acc.insert(key.to_owned(), workflows);
-            acc
-        });
+pub fn part_two(input: &str) -> ParseResult<u64> {
+    let workflows_map = parse_workflows(input)?;
     let mut total = 0;
     let mut queue: VecDeque<(PartRanges, &Result)> = VecDeque::new();
     let start = Result::Next(String::from("in"));
@@ -270,7 +272,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             }
         }
     }
-    Some(total as u64)
+    Ok(total as u64)
 }
 
 #[cfg(test)]
@@ -280,12 +282,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(19114));
+        assert_eq!(result, Ok(19114));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(167409079868000));
+        assert_eq!(result, Ok(167409079868000));
     }
 }
@@ -1,7 +1,13 @@
-advent_of_code::solution!(20);
+advent_of_code::solution!(
+    20,
+    shape = Lines,
+    pattern = r"^(broadcaster|[%&][a-z]+) -> [a-z]+(, [a-z]+)*$"
+);
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use advent_of_code::{ParseError, ParseResult};
+
 // % == flip-flop.  Off to start, changes when low pulse.  Off > on == high pulse.  On > off == low
 //   pulse.
 // & == conjunction.  Initially low pulse in each input.  Remembers values to inputs.  After
@@ -31,18 +37,21 @@ enum Module {
 }
 
 impl Module {
-    fn with_name_from(line: &str) -> (String, Self) {
-        let parts: Vec<_> = line.split(" -> ").collect();
-        if line.starts_with('%') {
-            let name = &parts[0][1..];
-            let outputs = parts[1].split(", ").map(|s| s.to_owned()).collect();
-            (name.to_owned(), Self::FlipFlop(false, outputs))
-        } else if line.starts_with('&') {
-            let name = &parts[0][1..];
-            let outputs = parts[1].split(", ").map(|s| s.to_owned()).collect();
-            (name.to_owned(), Self::Conjunction(Vec::new(), outputs))
+    fn parse_with_name(input: &str, line: &str) -> ParseResult<(String, Self)> {
+        let (module, outputs) = line
+            .split_once(" -> ")
+            .ok_or_else(|| ParseError::new(input, line, "expected a module like `%a -> b, c`"))?;
+        let outputs = outputs.split(", ").map(|s| s.to_owned()).collect();
+        if let Some(name) = module.strip_prefix('%') {
+            Ok((name.to_owned(), Self::FlipFlop(false, outputs)))
+        } else if let Some(name) = module.strip_prefix('&') {
+            Ok((name.to_owned(), Self::Conjunction(Vec::new(), outputs)))
         } else {
-            panic!("Unexpected line: {}", line);
+            Err(ParseError::new(
+                input,
+                module,
+                format!("unexpected module {module:?}, expected `broadcaster`, `%name` or `&name`"),
+            ))
         }
     }
 
@@ -165,41 +174,37 @@ fn press_button_part2(
     counts
 }
 
-fn modules_from_input(input: &str) -> (Vec<String>, HashMap<String, Module>, Option<String>) {
-    #[allow(clippy::type_complexity)]
-    let (broadcast, mut modules, inputs): (
-        Vec<String>,
-        HashMap<String, Module>,
-        HashMap<String, Vec<String>>,
-    ) = input.lines().fold(
-        (Vec::new(), HashMap::new(), HashMap::new()),
-        |mut acc, line| {
-            if line.starts_with("broadcaster") {
-                let broadcast = line
-                    .split(" -> ")
-                    .last()
-                    .unwrap()
-                    .split(", ")
-                    .map(|s| s.to_owned())
-                    .collect();
-                acc.0 = broadcast
-            } else {
-                let (name, module) = Module::with_name_from(line);
-                let outputs = match &module {
-                    Module::FlipFlop(_, outputs) => outputs.clone(),
-                    Module::Conjunction(_, outputs) => outputs.clone(),
-                };
-                for output in outputs.iter() {
-                    acc.2
-                        .entry(output.to_string())
-                        .and_modify(|v| v.push(name.clone()))
-                        .or_insert(vec![name.clone()]);
-                }
-                acc.1.insert(name, module);
+#[allow(clippy::type_complexity)]
+fn modules_from_input(
+    input: &str,
+) -> ParseResult<(Vec<String>, HashMap<String, Module>, Option<String>)> {
+    let mut broadcast = Vec::new();
+    let mut modules = HashMap::new();
+    let mut inputs: HashMap<String, Vec<String>> = HashMap::new();
+    for line in input.lines() {
+        if line.starts_with("broadcaster") {
+            broadcast = line
+                .split(" -> ")
+                .last()
+                .unwrap()
+                .split(", ")
+                .map(|s| s.to_owned())
+                .collect();
+        } else {
+            let (name, module) = Module::parse_with_name(input, line)?;
+            let outputs = match &module {
+                Module::FlipFlop(_, outputs) => outputs.clone(),
+                Module::Conjunction(_, outputs) => outputs.clone(),
+            };
+            for output in outputs.iter() {
+                inputs
+                    .entry(output.to_string())
+                    .and_modify(|v| v.push(name.clone()))
+                    .or_insert(vec![name.clone()]);
             }
-            acc
-        },
-    );
+            modules.insert(name, module);
+        }
+    }
     let rx_input = inputs.get("rx").cloned();
     inputs.into_iter().for_each(|(name, inputs)| {
         if let Some(m) = modules.get_mut(&name) {
@@ -208,10 +213,38 @@ fn modules_from_input(input: &str) -> (Vec<String>, HashMap<String, Module>, Opt
     });
     if let Some(v) = rx_input {
         if v.len() == 1 {
-            return (broadcast, modules, Some(v[0].clone()));
+            return Ok((broadcast, modules, Some(v[0].clone())));
         }
     }
-    (broadcast, modules, None)
+    Ok((broadcast, modules, None))
+}
+
+/// Points at the module that keeps part two from finding the single conjunction feeding `rx`,
+/// or at the end of the input if no module outputs to `rx` at all.
+fn rx_input_error(input: &str) -> ParseError {
+    let feeds_rx = |line: &&str| {
+        line.split_once(" -> ")
+            .is_some_and(|(_, outputs)| outputs.split(", ").any(|x| x.trim() == "rx"))
+    };
+    let mut feeders = input.lines().filter(feeds_rx);
+
+    match (feeders.next(), feeders.next()) {
+        (Some(_), Some(second)) => ParseError::new(
+            input,
+            second,
+            "expected a single module with the output `rx`",
+        ),
+        (Some(first), None) => ParseError::new(
+            input,
+            first,
+            "expected the module with the output `rx` to be a conjunction",
+        ),
+        (None, _) => ParseError::new(
+            input,
+            &input[input.trim_end().len()..],
+            "expected a conjunction module with the output `rx`",
+        ),
+    }
 }
 
 pub fn lcm(nums: &[usize]) -> usize {
@@ -230,8 +263,8 @@ fn gcd_of_two_numbers(a: usize, b: usize) -> usize {
     gcd_of_two_numbers(b, a % b)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (broadcast, mut modules, _) = modules_from_input(input);
+pub fn part_one(input: &str) -> ParseResult<u64> {
+    let (broadcast, mut modules, _) = modules_from_input(input)?;
 
     let mut seen: HashSet<Vec<Module>> = HashSet::new();
     let mut pulses = Vec::new();
@@ -246,16 +279,15 @@ pub fn part_one(input: &str) -> Option<u64> {
         .cycle()
         .take(1000)
         .fold((0, 0), |acc, item| (acc.0 + item.0, acc.1 + item.1));
-    Some((res.0 * res.1) as u64)
+    Ok((res.0 * res.1) as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (broadcast, mut modules, rx_input) = modules_from_input(input);
-    let rx_input_num_inputs = modules
-        .get(&rx_input.unwrap())
-        .unwrap()
-        .conj_state()
-        .expect("Input to rx must be a Conjunction")
+pub fn part_two(input: &str) -> ParseResult<u64> {
+    let (broadcast, mut modules, rx_input) = modules_from_input(input)?;
+    let rx_input_num_inputs = rx_input
+        .and_then(|name| modules.get(&name))
+        .and_then(|module| module.conj_state())
+        .ok_or_else(|| rx_input_error(input))?
         .len();
 
     let mut count = 0;
@@ -267,7 +299,7 @@ pub fn part_two(input: &str) -> Option<u64> {
             break;
         }
     }
-    Some(lcm(&counts) as u64)
+    Ok(lcm(&counts) as u64)
 }
 
 #[cfg(test)]
@@ -304,7 +336,17 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11687500));
+        assert_eq!(result, Ok(11687500));
+    }
+
+    #[test]
+    fn test_part_two_without_rx() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let error = part_two(&input).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 15));
+
+        let error = part_two("broadcaster -> a\n%a -> rx\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
     }
 
     // example doesn't work for part 2
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(21, shape = Grid, pattern = r"^[.#S]+$");
 
 pub fn valid_neighbors(point: (usize, usize), map: &Vec<Vec<char>>) -> Vec<(usize, usize)> {
     let mut ret = Vec::with_capacity(4);
@@ -3,7 +3,7 @@ use std::{
     collections::{BinaryHeap, HashMap, HashSet},
 };
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(22, shape = Lines, pattern = r"^\d+,\d+,\d+~\d+,\d+,\d+$");
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Point {
@@ -129,12 +129,14 @@ impl PartialEq for Block {
 
 fn drop_floating_blocks(blocks: Vec<Block>) -> (Vec<Block>, Vec<Vec<Block>>) {
     let mut blocks = blocks;
-    let highest_possible = blocks[blocks.len() - 1].highest_z();
-    let mut tops: Vec<Vec<Block>> = vec![Vec::new(); highest_possible];
-    let mut max = highest_possible;
+    // blocks are sorted by their bottom, so a tall block before the last one can reach higher
+    let highest_possible = blocks.iter().map(Block::highest_z).max().unwrap_or(0);
+    let mut tops: Vec<Vec<Block>> = vec![Vec::new(); highest_possible + 1];
+    let mut max = 0;
     blocks.iter_mut().for_each(|b| {
         if b.lowest_z() == 1 {
             tops[b.highest_z()].push(*b);
+            max = max.max(b.highest_z());
         } else {
             let mut indices = (1..b.lowest_z()).rev();
             let new_z = loop {
@@ -148,7 +150,7 @@ fn drop_floating_blocks(blocks: Vec<Block>) -> (Vec<Block>, Vec<Vec<Block>>) {
             };
             b.lower_to(new_z);
             tops[b.highest_z()].push(*b);
-            max = b.highest_z();
+            max = max.max(b.highest_z());
         }
     });
     (blocks, tops[0..=max].to_vec())
@@ -271,4 +273,30 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(7));
     }
+
+    #[test]
+    fn test_blocks_on_the_ground() {
+        let input = "0,0,1~0,0,1\n1,1,1~1,1,1\n";
+        assert_eq!(part_one(input), Some(2));
+        assert_eq!(part_two(input), Some(0));
+    }
+
+    #[test]
+    fn test_tall_block_above_the_last_one() {
+        // the last block falls below the top of the second one
+        let input = "0,0,1~0,0,1\n0,0,2~0,0,6\n1,1,3~1,1,7\n";
+        assert_eq!(part_one(input), Some(2));
+        assert_eq!(part_two(input), Some(1));
+    }
+
+    #[cfg(feature = "test_lib")]
+    #[test]
+    fn test_against_brute_force() {
+        use advent_of_code::template::testing::{check, day22};
+
+        check(1000, day22::generate, |input| {
+            assert_eq!(part_one(input), Some(day22::part_one(input)));
+            assert_eq!(part_two(input), Some(day22::part_two(input)));
+        });
+    }
 }
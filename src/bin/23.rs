@@ -1,4 +1,4 @@
-advent_of_code::solution!(23);
+advent_of_code::solution!(23, shape = Grid, pattern = r"^[.#<>^v]+$");
 
 use std::cmp::{self, Ordering};
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -1,6 +1,11 @@
+use advent_of_code::{ParseError, ParseResult};
 use z3::{ast::*, SatResult};
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(
+    24,
+    shape = Lines,
+    pattern = r"^-?\d+, +-?\d+, +-?\d+ @ +-?\d+, +-?\d+, +-?\d+$"
+);
 
 type Point = (i64, i64, i64);
 
@@ -11,23 +16,18 @@ struct Hailstone {
 }
 
 impl Hailstone {
-    fn from(s: &str) -> Self {
-        let mut seg = s.split(" @ ");
-        let point = seg
-            .next()
-            .unwrap()
-            .split(", ")
-            .map(|s| s.trim().parse::<i64>().unwrap())
-            .collect::<Vec<_>>();
-        let point = (point[0], point[1], point[2]);
-        let velocity = seg
-            .next()
-            .unwrap()
-            .split(", ")
-            .map(|s| s.trim().parse::<i64>().unwrap())
-            .collect::<Vec<_>>();
-        let velocity = (velocity[0], velocity[1], velocity[2]);
-        Self { point, velocity }
+    fn parse(input: &str, s: &str) -> ParseResult<Self> {
+        let (point, velocity) = s.split_once(" @ ").ok_or_else(|| {
+            ParseError::new(
+                input,
+                s,
+                "expected a hailstone like `19, 13, 30 @ -2, 1, -2`",
+            )
+        })?;
+        Ok(Self {
+            point: parse_point(input, point)?,
+            velocity: parse_point(input, velocity)?,
+        })
     }
 
     fn xy_pointslope(&self) -> (f64, f64) {
@@ -74,8 +74,34 @@ impl Hailstone {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let stones = input.lines().map(Hailstone::from).collect::<Vec<_>>();
+fn parse_point(input: &str, s: &str) -> ParseResult<Point> {
+    let coordinates = s
+        .split(',')
+        .map(|x| {
+            let x = x.trim();
+            x.parse::<i64>()
+                .map_err(|e| ParseError::new(input, x, format!("invalid coordinate {x:?}: {e}")))
+        })
+        .collect::<ParseResult<Vec<_>>>()?;
+    match coordinates[..] {
+        [x, y, z] => Ok((x, y, z)),
+        _ => Err(ParseError::new(
+            input,
+            s,
+            format!("expected 3 coordinates, got {}", coordinates.len()),
+        )),
+    }
+}
+
+fn parse_stones(input: &str) -> ParseResult<Vec<Hailstone>> {
+    input
+        .lines()
+        .map(|line| Hailstone::parse(input, line))
+        .collect()
+}
+
+pub fn part_one(input: &str) -> ParseResult<u64> {
+    let stones = parse_stones(input)?;
     let (min, max) = if stones.len() == 5 {
         (7.0, 27.0)
     } else {
@@ -93,11 +119,34 @@ pub fn part_one(input: &str) -> Option<u64> {
         });
         acc
     });
-    Some(intersections)
+    Ok(intersections)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let stones = input.lines().map(Hailstone::from).collect::<Vec<_>>();
+/// Why part two has no answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ThrowError {
+    Parse(ParseError),
+    /// The solver found no throw that hits every hailstone.
+    NoThrow(String),
+}
+
+impl std::fmt::Display for ThrowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{e}"),
+            Self::NoThrow(message) => write!(f, "error: {message}"),
+        }
+    }
+}
+
+impl From<ParseError> for ThrowError {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+pub fn part_two(input: &str) -> Result<u64, ThrowError> {
+    let stones = parse_stones(input)?;
 
     let z3_conf = z3::Config::new();
     let ctx = z3::Context::new(&z3_conf);
@@ -120,14 +169,20 @@ pub fn part_two(input: &str) -> Option<u64> {
     });
 
     let res = solver.check();
-    assert_eq!(res, SatResult::Sat);
+    if res != SatResult::Sat {
+        return Err(ThrowError::NoThrow(format!(
+            "no throw hits every hailstone, the solver returned {res:?}"
+        )));
+    }
     let res = solver
         .get_model()
-        .expect("Expected model to exist")
+        .ok_or_else(|| ThrowError::NoThrow("the solver found no model".into()))?
         .eval(&(&x + &y + &z), true)
-        .expect("Expected eval to work");
+        .ok_or_else(|| ThrowError::NoThrow("the solver could not evaluate the position".into()))?;
 
-    res.as_u64()
+    res.as_u64().ok_or_else(|| {
+        ThrowError::NoThrow(format!("the position sums to {res}, which is not a u64"))
+    })
 }
 
 #[cfg(test)]
@@ -137,12 +192,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        assert_eq!(result, Ok(2));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(47));
+        assert_eq!(result, Ok(47));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, shape = Lines, pattern = r"^[a-z]{3}:( [a-z]{3})+$");
 
 use std::collections::{HashMap, HashSet};
 
@@ -2,7 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of an event (i.e. an integer in range 1 to 99).
+/// Which days an event actually has is up to its [`Event`](crate::Event).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -16,10 +17,13 @@ use std::str::FromStr;
 pub struct Day(u8);
 
 impl Day {
+    /// The highest day number, days are named with two digits in file names.
+    pub const MAX: u8 = 99;
+
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > Self::MAX {
             return None;
         }
         Some(Self(day))
@@ -74,26 +78,36 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        f.write_str("expecting a day number between 1 and 99")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the default event, see [`Event::for_year`](crate::Event::for_year).
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    crate::Event::for_year(crate::Year::from_env()).days()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day from the 1st to a last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
+    /// An iterator from the 1st to the 25th, the days of Advent of Code events before 2025.
+    #[deprecated(note = "use `AllDays::until` or `Event::days`, events do not always have 25 days")]
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(Day(25))
+    }
+
+    pub fn until(last: Day) -> Self {
+        Self {
+            current: 1,
+            last: last.0,
+        }
     }
 }
 
@@ -101,10 +115,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -119,11 +133,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::Day::MAX,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and 99"
             ),
         );
         $crate::Day::__new_unchecked($day)
@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{day, AllDays, Day, Year};
+
+/// The calendar of an event: the days it has and the number of parts of each day.
+///
+/// Advent of Code events run from the 1st to the 25th (to the 12th since 2025), and the last day
+/// only has one part. Other calendars, e.g. of other puzzle series, are configured via
+/// `AOC_CALENDAR_<year>`, or via `AOC_CALENDAR` for the default year (see [`Year::from_env`]).
+///
+/// # Display
+/// This value displays as the last day, followed by the days that do not have two parts.
+///
+/// ```
+/// # use advent_of_code::{day, Event};
+/// let event: Event = "12,12:1".parse().unwrap();
+/// assert_eq!(event.last_day(), day!(12));
+/// assert_eq!(event.parts(day!(12)), 1);
+/// assert_eq!(event.to_string(), "12,12:1")
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Event {
+    /// The number of parts of each day, starting with the 1st.
+    parts: Vec<u8>,
+}
+
+impl Event {
+    /// Creates an event from the 1st to `last_day`, where every day has two parts.
+    pub fn new(last_day: Day) -> Self {
+        Self {
+            parts: vec![2; usize::from(last_day.into_inner())],
+        }
+    }
+
+    /// Sets the number of parts of a day, returns [`None`] if the event does not have the day or
+    /// the number of parts is not 1 or 2.
+    pub fn with_parts(mut self, day: Day, parts: u8) -> Option<Self> {
+        if !(1..=2).contains(&parts) {
+            return None;
+        }
+        *self.parts.get_mut(usize::from(day.into_inner()) - 1)? = parts;
+        Some(self)
+    }
+
+    /// The calendar of the Advent of Code event of a year.
+    pub fn advent_of_code(year: Year) -> Self {
+        let last_day = if year.into_inner() >= 2025 {
+            day!(12)
+        } else {
+            day!(25)
+        };
+
+        Self::new(last_day)
+            .with_parts(last_day, 1)
+            .expect("the last day is part of the event")
+    }
+
+    /// The calendar of an event, read from `AOC_CALENDAR_<year>` or, for the default year,
+    /// `AOC_CALENDAR`. Falls back to the Advent of Code calendar of the year.
+    pub fn for_year(year: Year) -> Self {
+        let configured = |name: &str| std::env::var(name).ok().and_then(|x| x.parse().ok());
+
+        configured(&format!("AOC_CALENDAR_{year}"))
+            .or_else(|| {
+                year.is_default()
+                    .then(|| configured("AOC_CALENDAR"))
+                    .flatten()
+            })
+            .unwrap_or_else(|| Self::advent_of_code(year))
+    }
+
+    /// The last day of the event.
+    pub fn last_day(&self) -> Day {
+        #[allow(clippy::cast_possible_truncation)]
+        Day::__new_unchecked(self.parts.len() as u8)
+    }
+
+    /// Whether the day is part of the event.
+    pub fn contains(&self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// The number of parts of a day, `0` for days that are not part of the event.
+    pub fn parts(&self, day: Day) -> u8 {
+        self.parts
+            .get(usize::from(day.into_inner()) - 1)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// An iterator that yields every day of the event.
+    pub fn days(&self) -> AllDays {
+        AllDays::until(self.last_day())
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.last_day().into_inner())?;
+        for day in self.days().filter(|x| self.parts(*x) != 2) {
+            write!(f, ",{}:{}", day.into_inner(), self.parts(day))?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Event {
+    type Err = EventFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut items = s.split(',').map(str::trim);
+
+        let last_day: Day = items
+            .next()
+            .and_then(|x| x.parse().ok())
+            .ok_or(EventFromStrError)?;
+
+        items.try_fold(Self::new(last_day), |event, item| {
+            let (day, parts) = item.split_once(':').ok_or(EventFromStrError)?;
+            let day: Day = day.trim().parse().map_err(|_| EventFromStrError)?;
+            let parts: u8 = parts.trim().parse().map_err(|_| EventFromStrError)?;
+            event.with_parts(day, parts).ok_or(EventFromStrError)
+        })
+    }
+}
+
+/// An error which can be returned when parsing an [`Event`].
+#[derive(Debug)]
+pub struct EventFromStrError;
+
+impl Error for EventFromStrError {}
+
+impl Display for EventFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting the last day of the event, optionally followed by the parts of days, e.g. `25,25:1`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Event;
+    use crate::{day, year};
+
+    #[test]
+    fn advent_of_code_calendars() {
+        let event = Event::advent_of_code(year!(2023));
+        assert_eq!(event.days().count(), 25);
+        assert_eq!(event.parts(day!(24)), 2);
+        assert_eq!(event.parts(day!(25)), 1);
+        assert_eq!(event.parts(day!(26)), 0);
+
+        let event = Event::advent_of_code(year!(2025));
+        assert_eq!(event.last_day(), day!(12));
+        assert_eq!(event.parts(day!(12)), 1);
+        assert!(!event.contains(day!(13)));
+    }
+
+    #[test]
+    fn parses_calendars() {
+        assert_eq!(
+            "25, 25:1".parse::<Event>().unwrap(),
+            Event::advent_of_code(year!(2023))
+        );
+        assert_eq!("20".parse::<Event>().unwrap().to_string(), "20");
+        assert!("".parse::<Event>().is_err());
+        assert!("12,13:1".parse::<Event>().is_err());
+        assert!("12,3:3".parse::<Event>().is_err());
+        assert!("12,3".parse::<Event>().is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
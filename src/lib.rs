@@ -1,4 +1,14 @@
+// lets the solution modules compiled into the registry refer to the crate by name.
+extern crate self as advent_of_code;
+
 mod day;
+mod event;
+mod parse_error;
+pub mod solutions;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use event::*;
+pub use parse_error::*;
+pub use year::*;
@@ -1,56 +1,205 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, download, extract, progress, read, scaffold, solve, verify,
+};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{num::NonZeroUsize, process};
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            benchmark_compare,
+            commands::{all, solve},
+            input::InputSource,
+            report::OutputFormat,
+            runner::BenchConfig,
+        },
+        Day, Event, Year,
+    };
 
     pub enum AppArguments {
-        Download {
-            day: Day,
-        },
-        Read {
-            day: Day,
-        },
-        Scaffold {
-            day: Day,
-        },
-        Solve {
-            day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-            time: bool,
-        },
+        Download { year: Year, day: Day },
+        Read { year: Year, day: Day, refresh: bool },
+        Extract { year: Year, day: Day, force: bool },
+        Scaffold { year: Year, day: Day },
+        Solve(solve::Options),
+        All(all::Options),
+        Verify { year: Year, record: bool },
+        Progress { year: Year },
+    }
+
+    /// Moves the given flags and their values out of the arguments, so they can be read by the
+    /// `from_args` parser the solution binaries use as well.
+    fn take_flags(
+        args: &mut pico_args::Arguments,
+        flags: &[&'static str],
+    ) -> Result<Vec<String>, pico_args::Error> {
+        let mut taken = vec![];
+        for flag in flags {
+            if let Some(value) = args.opt_value_from_str::<_, String>(*flag)? {
+                taken.extend([flag.to_string(), value]);
+            }
+        }
+        Ok(taken)
+    }
+
+    /// Reads `--year`, falling back to the default year. Options have to be read before free arguments.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::from_env))
+    }
+
+    /// Reads the day, which has to be part of the event (see [`Event::for_year`]).
+    fn parse_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        let day: Day = args.free_from_str()?;
+        let event = Event::for_year(year);
+
+        if !event.contains(day) {
+            return Err(format!(
+                "day {day} is not part of the {year} event, which ends on day {}",
+                event.last_day()
+            )
+            .into());
+        }
+
+        Ok(day)
+    }
+
+    fn parse_format(args: &mut pico_args::Arguments) -> Result<OutputFormat, pico_args::Error> {
+        Ok(args.opt_value_from_str("--format")?.unwrap_or_default())
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            Some("all") => {
+                let options = all::Options {
+                    year: parse_year(&mut args)?,
+                    is_release: args.contains("--release"),
+                    is_timed: args.contains("--time"),
+                    bench_config: BenchConfig::from_args(&take_flags(
+                        &mut args,
+                        &BenchConfig::FLAGS,
+                    )?)?,
+                    export: args.opt_value_from_str("--export")?,
+                    compare: args.opt_value_from_str("--compare")?,
+                    regression_threshold: args
+                        .opt_value_from_str("--regression-threshold")?
+                        .unwrap_or(benchmark_compare::DEFAULT_THRESHOLD_PERCENT),
+                    jobs: args
+                        .opt_value_from_str("--jobs")?
+                        .map_or(1, NonZeroUsize::get),
+                    format: parse_format(&mut args)?,
+                    history: args.opt_value_from_str("--history")?,
+                    part_timeout: args
+                        .opt_value_from_str("--timeout")?
+                        .map(std::time::Duration::from_millis),
+                    day_timeout: args
+                        .opt_value_from_str("--day-timeout")?
+                        .map(std::time::Duration::from_millis),
+                };
+
+                // concurrent runs compete for cores and caches, which would skew the timings.
+                if options.is_timed && options.jobs > 1 {
+                    return Err("--jobs can not be combined with --time".into());
+                }
+
+                AppArguments::All(options)
+            }
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+                record: args.contains("--record"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+            Some("progress") => AppArguments::Progress {
+                year: parse_year(&mut args)?,
             },
+            Some("download") => {
+                let year = parse_year(&mut args)?;
+                AppArguments::Download {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                }
+            }
+            Some("extract") => {
+                let year = parse_year(&mut args)?;
+                AppArguments::Extract {
+                    year,
+                    force: args.contains("--force"),
+                    day: parse_day(&mut args, year)?,
+                }
+            }
+            Some("read") => {
+                let year = parse_year(&mut args)?;
+                AppArguments::Read {
+                    year,
+                    refresh: args.contains("--refresh"),
+                    day: parse_day(&mut args, year)?,
+                }
+            }
+            Some("scaffold") => {
+                let year = parse_year(&mut args)?;
+                AppArguments::Scaffold {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                }
+            }
+            Some("solve") => {
+                let year = parse_year(&mut args)?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let time = args.contains("--time");
+                let bench_config =
+                    BenchConfig::from_args(&take_flags(&mut args, &BenchConfig::FLAGS)?)?;
+                let watch = args.contains("--watch");
+                let test = args.contains("--test");
+                let format = parse_format(&mut args)?;
+                let input_path: Option<String> = args.opt_value_from_str("--input")?;
+                let is_example = args.contains("--example");
+                let day = parse_day(&mut args, year)?;
+                // the example number is optional, so it can only be read after the day.
+                let example: Option<usize> = if is_example {
+                    Some(args.opt_free_from_str()?.unwrap_or(1))
+                } else {
+                    None
+                };
+
+                let input = InputSource::from_options(input_path, example)?;
+
+                let parts = Event::for_year(year).parts(day);
+                if submit.is_some_and(|part: u8| part == 0 || part > parts) {
+                    return Err(format!("day {day} has {parts} part(s) to submit").into());
+                }
+
+                if submit.is_some() && input != InputSource::Puzzle {
+                    return Err("--submit can only be used with the puzzle input".into());
+                }
+
+                if submit.is_some() && watch {
+                    return Err("--submit can not be combined with --watch".into());
+                }
+
+                if format == OutputFormat::Json && (submit.is_some() || watch) {
+                    return Err("--format json can not be combined with --submit or --watch".into());
+                }
+
+                AppArguments::Solve(solve::Options {
+                    year,
+                    day,
+                    release,
+                    time,
+                    submit_part: submit,
+                    bench_config,
+                    input,
+                    watch,
+                    test,
+                    format,
+                })
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -77,16 +226,14 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
+            AppArguments::All(options) => all::handle(&options),
+            AppArguments::Verify { year, record } => verify::handle(year, record),
+            AppArguments::Progress { year } => progress::handle(year),
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day, refresh } => read::handle(year, day, refresh),
+            AppArguments::Extract { year, day, force } => extract::handle(year, day, force),
+            AppArguments::Scaffold { year, day } => scaffold::handle(year, day),
+            AppArguments::Solve(options) => solve::handle(&options),
         },
     };
 }
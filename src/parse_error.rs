@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// An error found while parsing a puzzle input, pointing at the offending line and column.
+///
+/// # Display
+/// The error displays as a diagnostic that quotes the input line.
+///
+/// ```
+/// # use advent_of_code::ParseError;
+/// let input = "1, 2\n3; 4";
+/// let error = ParseError::new(input, &input[6..], "expected `,`");
+/// assert_eq!((error.line, error.column), (2, 2));
+/// assert_eq!(
+///     error.to_string(),
+///     "error: expected `,`\n --> line 2, column 2\n  |\n2 | 3; 4\n  |  ^\n"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// 1-based line number in the input.
+    pub line: usize,
+    /// 1-based column of the first offending character.
+    pub column: usize,
+    source_line: String,
+}
+
+/// The return type of solutions that parse their input with [`ParseError`]s.
+pub type ParseResult<T> = Result<T, ParseError>;
+
+impl ParseError {
+    /// Creates an error at the position of `at`, which has to be a slice of `input`
+    /// (e.g. a line returned by [`str::lines`], or a match within it).
+    ///
+    /// # Panics
+    /// In debug builds, if `at` is not a slice of `input`. Release builds point at the
+    /// start of the input instead.
+    pub fn new(input: &str, at: &str, message: impl Into<String>) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|x| *x + at.len() <= input.len() && input.is_char_boundary(*x));
+        debug_assert!(offset.is_some(), "{at:?} is not a slice of the input");
+        let offset = offset.unwrap_or_default();
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |x| x + 1);
+
+        Self {
+            message: message.into(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line: input[line_start..]
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{gutter}--> line {}, column {}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        writeln!(f, "{gutter} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+impl Error for ParseError {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn locates_slices_of_the_input() {
+        let input = "px{a<2006:qkq}\nin{q>1:A}\n";
+        let line = input.lines().nth(1).unwrap();
+
+        let error = ParseError::new(input, &line[3..], "unexpected category");
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = ParseError::new(input, &input[input.len()..], "unexpected end");
+        assert_eq!((error.line, error.column), (3, 1));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "\"elsewhere\" is not a slice of the input")]
+    fn rejects_slices_from_elsewhere() {
+        ParseError::new("px{a<2006:qkq}\n", "elsewhere", "unexpected category");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
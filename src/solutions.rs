@@ -0,0 +1,244 @@
+/// Module that exposes the solutions of the event as a library.
+/// Every `src/bin/DD.rs` is also compiled into the library (see [`registry`]), so other crates
+/// can depend on `advent_of_code` and solve a day without running its binary, see [`day`].
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::template::input::{self, InputError, Shape};
+use crate::template::registry;
+use crate::{Day, Year};
+
+/// A solved day of an event. Named apart from [`registry::Solution`], the entry it is implemented
+/// for.
+pub trait Puzzle: Sync {
+    fn year(&self) -> Year;
+
+    fn day(&self) -> Day;
+
+    /// The layout of the puzzle input, [`None`] if the day does not declare one.
+    fn shape(&self) -> Option<Shape>;
+
+    /// A regex every line of the puzzle input that is not blank matches, [`None`] if the day
+    /// does not declare one.
+    fn pattern(&self) -> Option<&'static str>;
+
+    /// Normalises a raw puzzle input and checks it against the [`shape`](Puzzle::shape) and
+    /// the [`pattern`](Puzzle::pattern) of the day. The parts prepare their input themselves.
+    fn prepare_input(&self, input: &str) -> Result<String, InputError> {
+        let input = input::normalize(input);
+        input::validate(&input, self.shape(), self.pattern())?;
+        Ok(input)
+    }
+
+    /// Solves part one, [`None`] if it is not solved yet.
+    fn part_one(&self, input: &str) -> Result<Option<Answer>, SolveError>;
+
+    /// Solves part two, [`None`] if it is not solved yet.
+    fn part_two(&self, input: &str) -> Result<Option<Answer>, SolveError>;
+}
+
+impl Puzzle for registry::Solution {
+    fn year(&self) -> Year {
+        registry::Solution::year(self)
+    }
+
+    fn day(&self) -> Day {
+        self.day
+    }
+
+    fn shape(&self) -> Option<Shape> {
+        self.shape
+    }
+
+    fn pattern(&self) -> Option<&'static str> {
+        self.pattern
+    }
+
+    fn part_one(&self, input: &str) -> Result<Option<Answer>, SolveError> {
+        (self.answer_one)(&self.prepare_input(input)?).map_err(SolveError::Part)
+    }
+
+    fn part_two(&self, input: &str) -> Result<Option<Answer>, SolveError> {
+        (self.answer_two)(&self.prepare_input(input)?).map_err(SolveError::Part)
+    }
+}
+
+/// The main solution of a day of the default year (see [`Year::from_env`]), if it has been
+/// scaffolded.
+///
+/// ```
+/// # use advent_of_code::solutions::{self, Answer};
+/// let input = "1abc2\npqr3stu8vwx\n";
+/// let day = solutions::day(1).expect("day 1 is solved");
+/// assert_eq!(day.part_one(input).unwrap(), Some(Answer::Integer(50)));
+/// ```
+#[must_use]
+pub fn day(day: u8) -> Option<&'static dyn Puzzle> {
+    get(Year::from_env(), Day::new(day)?)
+}
+
+/// The main solution of a day of an event, if it has been scaffolded.
+#[must_use]
+pub fn get(year: Year, day: Day) -> Option<&'static dyn Puzzle> {
+    registry::get(year, day).map(|x| x as &dyn Puzzle)
+}
+
+/// The answer to a part, typed as returned by the solution.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(x) => write!(f, "{x}"),
+            Self::Text(x) => f.write_str(x),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(x: $t) -> Self {
+                Self::Integer(i128::from(x))
+            }
+        })*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl From<usize> for Answer {
+    fn from(x: usize) -> Self {
+        Self::Integer(x as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(x: String) -> Self {
+        Self::Text(x)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(x: &str) -> Self {
+        Self::Text(x.to_string())
+    }
+}
+
+/// Converts the return value of a `part_one` or `part_two` function into an [`Answer`].
+pub trait IntoAnswer {
+    /// The answer, or the formatted error of a `Result` part.
+    fn into_answer(self) -> Result<Option<Answer>, String>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Result<Option<Answer>, String> {
+        Ok(self.map(Into::into))
+    }
+}
+
+impl<T: Into<Answer>, E: Display> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Option<Answer>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.into())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SolveError {
+    /// The input is empty or does not have the shape or the line pattern of the day.
+    Input(InputError),
+    /// The part returned an error, e.g. a [`crate::ParseError`] diagnostic.
+    Part(String),
+}
+
+impl Error for SolveError {}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Input(e) => write!(f, "invalid input: {e}"),
+            Self::Part(e) => f.write_str(e),
+        }
+    }
+}
+
+impl From<InputError> for SolveError {
+    fn from(e: InputError) -> Self {
+        Self::Input(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{day, Answer, Puzzle, SolveError};
+    use crate::template::{input::InputError, read_file};
+
+    fn solved(x: u8) -> &'static dyn Puzzle {
+        day(x).unwrap_or_else(|| panic!("day {x} is not solved"))
+    }
+
+    #[test]
+    fn solves_days_in_process() {
+        // the example of the documentation of `day`.
+        let input = "1abc2\npqr3stu8vwx\n";
+        assert_eq!(
+            solved(1).part_one(input).unwrap(),
+            Some(Answer::Integer(50))
+        );
+
+        let input = read_file("examples", crate::day!(1));
+        assert_eq!(
+            solved(1).part_one(&input).unwrap(),
+            Some(Answer::Integer(142 + 209))
+        );
+        assert_eq!(solved(1).day(), crate::day!(1));
+
+        // CRLF line endings are normalised before the part sees them.
+        let input = read_file("examples", crate::day!(14)).replace('\n', "\r\n");
+        assert_eq!(
+            solved(14).part_one(&input).unwrap(),
+            Some(Answer::Integer(136))
+        );
+        assert_eq!(
+            solved(14).prepare_input(&input).unwrap(),
+            read_file("examples", crate::day!(14))
+        );
+    }
+
+    #[test]
+    fn finds_only_scaffolded_days() {
+        assert!(day(1).is_some());
+        assert!(day(0).is_none());
+        assert!(day(26).is_none());
+    }
+
+    #[test]
+    fn reports_invalid_inputs() {
+        assert!(matches!(
+            solved(14).part_one(""),
+            Err(SolveError::Input(InputError::Empty))
+        ));
+        assert!(matches!(
+            solved(14).part_one("O..\n#.\n"),
+            Err(SolveError::Input(InputError::Shape { .. }))
+        ));
+        assert!(matches!(
+            solved(19).part_one("{\n\n{x=1}"),
+            Err(SolveError::Input(InputError::Pattern { line: 1, .. }))
+        ));
+        // the input looks like day 20, but no module outputs to `rx`.
+        assert!(matches!(
+            solved(20).part_two("broadcaster -> a\n%a -> b\n"),
+            Err(SolveError::Part(_))
+        ));
+    }
+}
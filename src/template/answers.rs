@@ -0,0 +1,145 @@
+/// Module for the expected answers of a puzzle input.
+/// Answers are stored as one `part_one: <answer>` / `part_two: <answer>` line per part, both next
+/// to the examples (`data/examples/DD.answers`) and in the manifest of accepted answers for the
+/// real inputs (`data/answers/DD.answers`).
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::data_dir;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answers: {e}"),
+            Error::IO(e) => write!(f, "could not access answers: {e}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for line in content.lines().filter(|x| !x.trim().is_empty()) {
+            let (key, answer) = line.split_once(':').ok_or_else(|| {
+                Error::Parser(format!("expected `part_one: <answer>`, got {line:?}"))
+            })?;
+
+            let part = match key.trim() {
+                "part_one" => 1,
+                "part_two" => 2,
+                key => return Err(Error::Parser(format!("unknown part {key:?}"))),
+            };
+
+            answers.set(part, answer.trim());
+        }
+
+        Ok(answers)
+    }
+
+    /// Loads answers from a file. A missing file is treated as no known answers.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Sets the answer of a part (`1` or `2`), other part numbers are ignored.
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_one = Some(answer.to_string()),
+            2 => self.part_two = Some(answer.to_string()),
+            _ => {}
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.part_one.is_none() && self.part_two.is_none()
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(answer) = &self.part_one {
+            writeln!(f, "part_one: {answer}")?;
+        }
+        if let Some(answer) = &self.part_two {
+            writeln!(f, "part_two: {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Path of the accepted answers for the real input of a day.
+#[must_use]
+pub fn manifest_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year)
+        .join("answers")
+        .join(format!("{day}.answers"))
+}
+
+/// Adds an accepted answer to the manifest.
+pub fn record(year: Year, day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let path = manifest_path(year, day);
+    let mut answers = Answers::load(&path)?;
+    answers.set(part, answer);
+    answers.save(&path)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn round_trips_answers() {
+        let answers = Answers::parse("part_one: 54159\n\npart_two:  53866 \n").unwrap();
+        assert_eq!(answers.get(1), Some("54159"));
+        assert_eq!(answers.get(2), Some("53866"));
+        assert_eq!(answers.to_string(), "part_one: 54159\npart_two: 53866\n");
+
+        assert!(Answers::parse("part_three: 1").is_err());
+        assert!(Answers::parse("54159").is_err());
+    }
+}
@@ -0,0 +1,265 @@
+//! Converts the puzzle page served by adventofcode.com into markdown.
+//! Only the small subset of HTML used inside `<article class="day-desc">` is supported.
+//!
+//! Highlighted values such as `<code><em>142</em></code>` are written as ``*`142`*``,
+//! which is how example answers can be recognized in the stored markdown.
+
+/// Returns the markdown of every puzzle description (one per unlocked part) on the page.
+#[must_use]
+pub fn puzzle_to_markdown(page: &str) -> String {
+    let mut out = String::new();
+    let mut rest = page;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(body_start) = rest[start..].find('>').map(|x| start + x + 1) else {
+            break;
+        };
+        let Some(body_end) = rest[body_start..]
+            .find("</article>")
+            .map(|x| body_start + x)
+        else {
+            break;
+        };
+
+        out.push_str(&to_markdown(&rest[body_start..body_end]));
+        rest = &rest[body_end + "</article>".len()..];
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Returns the text of the first `<article>` on the page, which is where
+/// the server explains the outcome of a submission. [`None`] if the page has no article.
+#[must_use]
+pub fn article_text(page: &str) -> Option<String> {
+    match (page.find("<article"), page.find("</article>")) {
+        (Some(start), Some(end)) if start < end => Some(text(&page[start..end])),
+        _ => None,
+    }
+}
+
+/// Whether the page asks to log in, i.e. links to the login of one of the auth providers
+/// (`/auth/github`, ...).
+#[must_use]
+pub fn is_login_page(page: &str) -> bool {
+    tokenize(page).iter().any(|token| match token {
+        Token::Tag {
+            name,
+            is_closing: false,
+            attrs,
+        } => name == "a" && attr_value(attrs, "href").is_some_and(|x| x.starts_with("/auth/")),
+        _ => false,
+    })
+}
+
+/// Returns the text of an HTML snippet, with whitespace collapsed.
+#[must_use]
+pub fn text(html: &str) -> String {
+    tokenize(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            Token::Tag { .. } => None,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Tag {
+        name: String,
+        is_closing: bool,
+        attrs: String,
+    },
+    Text(String),
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        match rest.find('<') {
+            Some(0) => {
+                // an unterminated `<` is not a tag, keep it as text.
+                let Some(end) = rest.find('>') else {
+                    tokens.push(Token::Text(decode_entities(rest)));
+                    break;
+                };
+                let inner = rest[1..end].trim().trim_end_matches('/');
+                let is_closing = inner.starts_with('/');
+                let inner = inner.trim_start_matches('/');
+                let (name, attrs) = inner.split_once(char::is_whitespace).unwrap_or((inner, ""));
+                tokens.push(Token::Tag {
+                    name: name.to_ascii_lowercase(),
+                    is_closing,
+                    attrs: attrs.to_string(),
+                });
+                rest = &rest[end + 1..];
+            }
+            Some(next) => {
+                tokens.push(Token::Text(decode_entities(&rest[..next])));
+                rest = &rest[next..];
+            }
+            None => {
+                tokens.push(Token::Text(decode_entities(rest)));
+                rest = "";
+            }
+        }
+    }
+
+    tokens
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn attr_value(attrs: &str, key: &str) -> Option<String> {
+    let start = attrs.find(&format!("{key}=\""))? + key.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(decode_entities(&attrs[start..start + len]))
+}
+
+/// Converts the inner HTML of one article.
+fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+
+    let mut in_pre = false;
+    // text and highlight state of an inline `<code>` element.
+    let mut inline_code: Option<(String, bool)> = None;
+    let mut links: Vec<String> = vec![];
+
+    for token in tokenize(html) {
+        match token {
+            Token::Text(text) => {
+                if in_pre {
+                    out.push_str(&text);
+                } else if let Some((code, _)) = inline_code.as_mut() {
+                    code.push_str(&text);
+                } else {
+                    let text = text.replace('\n', " ");
+                    // whitespace between block elements would otherwise indent the next line.
+                    if out.is_empty() || out.ends_with('\n') {
+                        out.push_str(text.trim_start());
+                    } else {
+                        out.push_str(&text);
+                    }
+                }
+            }
+            Token::Tag {
+                name,
+                is_closing,
+                attrs,
+            } => match (name.as_str(), is_closing) {
+                ("h2", false) => out.push_str("## "),
+                ("h2" | "p", true) => out.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                ("code", false) if !in_pre => inline_code = Some((String::new(), false)),
+                ("code", true) if !in_pre => {
+                    if let Some((code, is_highlighted)) = inline_code.take() {
+                        if is_highlighted {
+                            out.push_str(&format!("*`{code}`*"));
+                        } else {
+                            out.push_str(&format!("`{code}`"));
+                        }
+                    }
+                }
+                ("em", _) if in_pre => {}
+                ("em", false) if inline_code.is_some() => {
+                    if let Some((_, is_highlighted)) = inline_code.as_mut() {
+                        *is_highlighted = true;
+                    }
+                }
+                ("em", true) if inline_code.is_some() => {}
+                ("em", _) => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li", true) => out.push('\n'),
+                ("ul", true) => out.push('\n'),
+                ("a", false) => {
+                    links.push(attr_value(&attrs, "href").unwrap_or_default());
+                    out.push('[');
+                }
+                ("a", true) => {
+                    let href = links.pop().unwrap_or_default();
+                    out.push_str(&format!("]({href})"));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{article_text, puzzle_to_markdown, text};
+
+    #[test]
+    fn converts_puzzle_description() {
+        let page = concat!(
+            "<html><main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>",
+            "<p>For example:</p>\n<pre><code>1abc2\n<em>pqr3stu8vwx</em>\n</code></pre>\n",
+            "<p>Adding these together produces <code><em>142</em></code>, see ",
+            "<a href=\"/2023/day/1/input\">your input</a> &amp; <em>more</em>.</p>\n",
+            "<ul><li>one</li><li><code>two</code></li></ul></article>",
+            "<p>Your puzzle answer was <code>54159</code>.</p></main></html>",
+        );
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "Adding these together produces *`142`*, see [your input](/2023/day/1/input) & *more*.",
+            "",
+            "- one",
+            "- `two`",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(puzzle_to_markdown(page), expected);
+    }
+
+    #[test]
+    fn extracts_submission_message() {
+        let page = "<main><article><p>That's not the right answer; your answer is too high.  <a href=\"/2023/day/1\">[Return to Day 1]</a></p></article></main>";
+        assert_eq!(
+            article_text(page).as_deref(),
+            Some("That's not the right answer; your answer is too high. [Return to Day 1]")
+        );
+        assert_eq!(article_text("<main><p>Please log in.</p></main>"), None);
+    }
+
+    #[test]
+    fn keeps_unterminated_tags_as_text() {
+        assert_eq!(text("<p>1 < 2 <"), "1 < 2 <");
+        assert_eq!(text("<"), "<");
+    }
+}
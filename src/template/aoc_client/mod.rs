@@ -0,0 +1,436 @@
+/// Built-in client for the adventofcode.com website, used to download inputs and puzzles and to submit answers.
+///
+/// Configuration is read from the environment:
+///  - `AOC_SESSION`: the session cookie. Falls back to the file at `AOC_SESSION_FILE`,
+///    or `~/.adventofcode.session` (the location used by aoc-cli).
+///  - `AOC_YEAR`: the default event year, see [`Year::from_env`]. Defaults to the latest event.
+///  - `AOC_BASE_URL`: defaults to `https://adventofcode.com`, can point at a local mock server.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::data_dir;
+use crate::{Day, Year};
+
+pub mod html;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/BakerNet/aoc2023 (advent_of_code template)";
+
+/// The hint given alongside a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+impl Display for AnswerHint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswerHint::TooHigh => write!(f, "too high"),
+            AnswerHint::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was configured.
+    MissingSession,
+    /// The server did not accept the session cookie.
+    NotLoggedIn,
+    PuzzleNotUnlocked,
+    /// Requests or submissions were sent too quickly. Contains the server's message if there was one.
+    RateLimited(Option<String>),
+    WrongAnswer(Option<AnswerHint>),
+    /// The part was already solved, or part one has to be solved first.
+    WrongLevel,
+    UnexpectedResponse(u16, String),
+    Transport(String),
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or write it to ~/.adventofcode.session."
+            ),
+            AocClientError::NotLoggedIn => {
+                write!(
+                    f,
+                    "not logged in, the session cookie is invalid or expired."
+                )
+            }
+            AocClientError::PuzzleNotUnlocked => write!(f, "the puzzle has not been unlocked yet."),
+            AocClientError::RateLimited(Some(message)) => write!(f, "rate limited: {message}"),
+            AocClientError::RateLimited(None) => write!(f, "rate limited, try again later."),
+            AocClientError::WrongAnswer(Some(hint)) => {
+                write!(f, "that's not the right answer, it is {hint}.")
+            }
+            AocClientError::WrongAnswer(None) => write!(f, "that's not the right answer."),
+            AocClientError::WrongLevel => {
+                write!(f, "this part is already solved or not available yet.")
+            }
+            AocClientError::UnexpectedResponse(status, message) => {
+                write!(f, "unexpected response ({status}): {message}")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+            AocClientError::IoError(e) => {
+                write!(f, "could not write output files to file system: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            // the site redirects to the login page for anonymous requests.
+            .redirects(0)
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent,
+        }
+    }
+
+    /// Creates a client for the event of `year`, configured from the environment (see the module
+    /// documentation).
+    pub fn for_year(year: Year) -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &read_session()?, year))
+    }
+
+    #[must_use]
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    pub fn fetch_input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("day/{}/input", day.into_inner()))
+    }
+
+    /// Fetches the puzzle description as markdown. Contains part two once part one has been solved.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let page = self.get(&format!("day/{}", day.into_inner()))?;
+        Ok(html::puzzle_to_markdown(&page))
+    }
+
+    /// Submits an answer. Returns `Ok` when the answer was correct.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<(), AocClientError> {
+        let url = self.url(&format!("day/{}/answer", day.into_inner()));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let page = read_response(response)?;
+        parse_submission(&page)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}/{path}", self.base_url, self.year)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .call();
+        read_response(response)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) if (300..400).contains(&response.status()) => Err(AocClientError::NotLoggedIn),
+        Ok(response) => response.into_string().map_err(AocClientError::IoError),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(match status {
+                400 | 401 | 403 => AocClientError::NotLoggedIn,
+                404 => AocClientError::PuzzleNotUnlocked,
+                429 => AocClientError::RateLimited(None),
+                _ => AocClientError::UnexpectedResponse(status, body.trim().to_string()),
+            })
+        }
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/// Maps the submission response to a result. The outcome is explained in the article of the
+/// page, a page without one either asks to log in or is not a submission response.
+fn parse_submission(page: &str) -> Result<(), AocClientError> {
+    let Some(message) = html::article_text(page) else {
+        return Err(if html::is_login_page(page) {
+            AocClientError::NotLoggedIn
+        } else {
+            AocClientError::UnexpectedResponse(200, html::text(page))
+        });
+    };
+
+    if message.contains("That's the right answer") {
+        Ok(())
+    } else if message.contains("That's not the right answer") {
+        let hint = if message.contains("too high") {
+            Some(AnswerHint::TooHigh)
+        } else if message.contains("too low") {
+            Some(AnswerHint::TooLow)
+        } else {
+            None
+        };
+        Err(AocClientError::WrongAnswer(hint))
+    } else if message.contains("You gave an answer too recently") {
+        Err(AocClientError::RateLimited(Some(message)))
+    } else if message.contains("You don't seem to be solving the right level") {
+        Err(AocClientError::WrongLevel)
+    } else {
+        Err(AocClientError::UnexpectedResponse(200, message))
+    }
+}
+
+fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Ok(session);
+        }
+    }
+
+    let path = env::var("AOC_SESSION_FILE")
+        .map(PathBuf::from)
+        .or_else(|_| {
+            env::var("HOME").map(|home| PathBuf::from(home).join(".adventofcode.session"))
+        });
+
+    path.ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .ok_or(AocClientError::MissingSession)
+}
+
+pub fn get_input_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("inputs").join(format!("{day}.txt"))
+}
+
+pub fn get_puzzle_path(year: Year, day: Day) -> PathBuf {
+    data_dir(year).join("puzzles").join(format!("{day}.md"))
+}
+
+/// Writes a file, creating the year-scoped data folders on first use.
+fn write_data_file(path: &PathBuf, content: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)
+}
+
+/// Downloads input and puzzle description of a day to the `data` folder.
+pub fn download(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::for_year(year)?;
+
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    // both are fetched before anything is written, so a failed request leaves no partial download.
+    let input = client.fetch_input(day)?;
+    let puzzle = client.fetch_puzzle(day)?;
+
+    write_data_file(&input_path, &input)?;
+    write_data_file(&puzzle_path, &puzzle)?;
+
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Fetches the puzzle description of a day, storing it to the `data` folder. Returns the markdown.
+pub fn read(year: Year, day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::for_year(year)?;
+    let puzzle = client.fetch_puzzle(day)?;
+    write_data_file(&get_puzzle_path(year, day), &puzzle)?;
+    Ok(puzzle)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_submission, AnswerHint, AocClient, AocClientError};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serves one canned response per entry and returns the base url plus the received request lines.
+    fn mock_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut content_length = 0;
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    let lower = header.to_ascii_lowercase();
+                    if let Some(value) = lower.strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    if lower.starts_with("cookie:") {
+                        cookie = header.trim().to_string();
+                    }
+                }
+
+                let mut form = vec![0; content_length];
+                reader.read_exact(&mut form).unwrap();
+
+                requests.push(format!(
+                    "{} {cookie} {}",
+                    request_line.trim(),
+                    String::from_utf8_lossy(&form)
+                ));
+
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (base_url, server) = mock_server(vec![(200, "1abc2\n")]);
+        let client = AocClient::new(&base_url, "secret\n", year!(2023));
+
+        assert_eq!(client.fetch_input(day!(1)).unwrap(), "1abc2\n");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2023/day/1/input HTTP/1.1 Cookie: session=secret"));
+    }
+
+    #[test]
+    fn maps_error_responses() {
+        let (base_url, server) = mock_server(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (500, "Internal Server Error"),
+        ]);
+        let client = AocClient::new(&base_url, "secret", year!(2023));
+
+        assert!(matches!(
+            client.fetch_input(day!(1)),
+            Err(AocClientError::NotLoggedIn)
+        ));
+        assert!(matches!(
+            client.fetch_puzzle(day!(25)),
+            Err(AocClientError::PuzzleNotUnlocked)
+        ));
+        assert!(matches!(
+            client.fetch_input(day!(2)),
+            Err(AocClientError::UnexpectedResponse(500, _))
+        ));
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = mock_server(vec![
+            (200, "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>"),
+            (200, "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>"),
+            (200, "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 37s left to wait.</p></article></main>"),
+        ]);
+        let client = AocClient::new(&base_url, "secret", year!(2023));
+
+        assert!(client.submit(day!(1), 1, "142").is_ok());
+        assert!(matches!(
+            client.submit(day!(1), 2, "1"),
+            Err(AocClientError::WrongAnswer(Some(AnswerHint::TooLow)))
+        ));
+        assert!(matches!(
+            client.submit(day!(1), 2, "2"),
+            Err(AocClientError::RateLimited(Some(_)))
+        ));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2023/day/1/answer"));
+        assert!(requests[0].ends_with("level=1&answer=142"));
+    }
+
+    #[test]
+    fn recognizes_login_pages() {
+        let page = "<main><p>To play, please identify yourself via one of these services:</p><p><a href=\"/auth/github\">[GitHub]</a></p></main>";
+        assert!(matches!(
+            parse_submission(page),
+            Err(AocClientError::NotLoggedIn)
+        ));
+
+        // text about logging in is not enough, e.g. in an unknown message of the server.
+        let page = "<main><article><p>Please log in again later.</p></article></main>";
+        assert!(matches!(
+            parse_submission(page),
+            Err(AocClientError::UnexpectedResponse(200, _))
+        ));
+    }
+}
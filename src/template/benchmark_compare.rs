@@ -0,0 +1,200 @@
+/// Module that compares the current benchmark results against a previously exported baseline.
+use std::{fmt::Write as _, time::Duration};
+
+use crate::template::{benchmark_export::Export, ANSI_BOLD, ANSI_RESET};
+
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+/// Timing of one part in the baseline and the current run. Either side can be missing
+/// when a part was added or stopped being solved in between.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Delta {
+    pub day: u8,
+    pub part: u8,
+    pub baseline_nanos: Option<u64>,
+    pub current_nanos: Option<u64>,
+}
+
+impl Delta {
+    /// Relative change in percent, positive values mean the part got slower.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn change_percent(&self) -> Option<f64> {
+        let (baseline, current) = (self.baseline_nanos?, self.current_nanos?);
+        if baseline == 0 {
+            return None;
+        }
+        Some((current as f64 - baseline as f64) / baseline as f64 * 100.0)
+    }
+
+    #[must_use]
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent()
+            .is_some_and(|change| change > threshold_percent)
+    }
+
+    /// Whether the part has a baseline timing but did not produce one in the current run,
+    /// e.g. because the day stopped solving it.
+    #[must_use]
+    pub fn is_missing(&self) -> bool {
+        self.baseline_nanos.is_some() && self.current_nanos.is_none()
+    }
+}
+
+pub struct Comparison {
+    pub deltas: Vec<Delta>,
+    pub threshold_percent: f64,
+}
+
+impl Comparison {
+    #[must_use]
+    pub fn new(baseline: &Export, current: &Export, threshold_percent: f64) -> Self {
+        let nanos = |export: &Export, day: u8, part: u8| {
+            export
+                .days
+                .iter()
+                .find(|x| x.day == day)
+                .and_then(|x| x.parts.iter().find(|x| x.part == part))
+                .map(|x| x.nanos)
+        };
+
+        let mut keys: Vec<(u8, u8)> = baseline
+            .days
+            .iter()
+            .chain(current.days.iter())
+            .flat_map(|day| day.parts.iter().map(|part| (day.day, part.part)))
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        let deltas = keys
+            .into_iter()
+            .map(|(day, part)| Delta {
+                day,
+                part,
+                baseline_nanos: nanos(baseline, day, part),
+                current_nanos: nanos(current, day, part),
+            })
+            .collect();
+
+        Self {
+            deltas,
+            threshold_percent,
+        }
+    }
+
+    pub fn regressions(&self) -> impl Iterator<Item = &Delta> {
+        self.deltas
+            .iter()
+            .filter(|x| x.is_regression(self.threshold_percent))
+    }
+
+    #[must_use]
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+
+    /// Parts of the baseline that are missing from the current run, see [`Delta::is_missing`].
+    pub fn missing(&self) -> impl Iterator<Item = &Delta> {
+        self.deltas.iter().filter(|x| x.is_missing())
+    }
+
+    /// Renders the comparison as a fixed-width table for the terminal.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "{:>4} | {:>4} | {:>10} | {:>10} | {:>8} |",
+            "Day", "Part", "Baseline", "Current", "Delta"
+        );
+        let _ = writeln!(out, "{}", "-".repeat(50));
+
+        for delta in &self.deltas {
+            let change = match delta.change_percent() {
+                Some(change) => format!("{change:+.1}%"),
+                None => "-".into(),
+            };
+
+            let flag = if delta.is_regression(self.threshold_percent) {
+                format!(" {ANSI_BOLD}▲ regression{ANSI_RESET}")
+            } else if delta.is_missing() {
+                format!(" {ANSI_BOLD}✖ missing{ANSI_RESET}")
+            } else {
+                String::new()
+            };
+
+            let _ = writeln!(
+                out,
+                "{:>4} | {:>4} | {:>10} | {:>10} | {:>8} |{flag}",
+                format!("{:02}", delta.day),
+                delta.part,
+                format_nanos(delta.baseline_nanos),
+                format_nanos(delta.current_nanos),
+                change,
+            );
+        }
+
+        out
+    }
+}
+
+fn format_nanos(nanos: Option<u64>) -> String {
+    match nanos {
+        Some(nanos) => format!("{:.1?}", Duration::from_nanos(nanos)),
+        None => "-".into(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Comparison, Delta};
+    use crate::template::benchmark_export::{DayRecord, Export, PartRecord};
+
+    fn export(parts: &[(u8, u8, u64)]) -> Export {
+        let mut export = Export::default();
+        for &(day, part, nanos) in parts {
+            let record = PartRecord {
+                part,
+                nanos,
+                ..PartRecord::default()
+            };
+            match export.days.iter_mut().find(|x| x.day == day) {
+                Some(x) => x.parts.push(record),
+                None => export.days.push(DayRecord {
+                    day,
+                    parts: vec![record],
+                }),
+            }
+        }
+        export
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let baseline = export(&[(1, 1, 100), (1, 2, 100), (2, 1, 100)]);
+        let current = export(&[(1, 1, 105), (1, 2, 150), (2, 1, 50)]);
+        let comparison = Comparison::new(&baseline, &current, 10.0);
+
+        let regressions: Vec<&Delta> = comparison.regressions().collect();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!((regressions[0].day, regressions[0].part), (1, 2));
+        assert_eq!(regressions[0].change_percent(), Some(50.0));
+    }
+
+    #[test]
+    fn keeps_parts_missing_on_either_side() {
+        let baseline = export(&[(1, 1, 100), (3, 1, 100)]);
+        let current = export(&[(1, 1, 100), (2, 1, 100)]);
+        let comparison = Comparison::new(&baseline, &current, 10.0);
+
+        assert_eq!(comparison.deltas.len(), 3);
+        assert_eq!(comparison.deltas[1].baseline_nanos, None);
+        assert_eq!(comparison.deltas[2].current_nanos, None);
+        assert!(!comparison.has_regressions());
+
+        let missing: Vec<&Delta> = comparison.missing().collect();
+        assert_eq!(missing.len(), 1);
+        assert_eq!((missing[0].day, missing[0].part), (3, 1));
+    }
+}
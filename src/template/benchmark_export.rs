@@ -0,0 +1,411 @@
+/// Module that writes benchmark results to machine-readable files (JSON or CSV),
+/// so they can be consumed by tooling other than the README table.
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{readme_benchmarks::Timings, runner::BenchStats};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark export: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark export: {e}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl Format {
+    /// Picks the format based on the file extension, defaulting to JSON.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|x| x.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+            _ => Format::Json,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Host {
+    pub hostname: Option<String>,
+    pub os: String,
+    pub arch: String,
+    pub cpus: Option<usize>,
+}
+
+impl Host {
+    #[must_use]
+    pub fn current() -> Self {
+        let hostname = std::env::var("HOSTNAME")
+            .ok()
+            .or_else(|| fs::read_to_string("/etc/hostname").ok())
+            .map(|x| x.trim().to_string())
+            .filter(|x| !x.is_empty());
+
+        Self {
+            hostname,
+            os: std::env::consts::OS.into(),
+            arch: std::env::consts::ARCH.into(),
+            cpus: std::thread::available_parallelism().map(|x| x.get()).ok(),
+        }
+    }
+}
+
+/// Timing statistics of a single part, with every duration stored as nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub part: u8,
+    pub nanos: u64,
+    pub samples: u64,
+    pub min_nanos: u64,
+    pub median_nanos: u64,
+    pub mean_nanos: u64,
+    pub p95_nanos: u64,
+    pub max_nanos: u64,
+    pub std_dev_nanos: u64,
+}
+
+impl PartRecord {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(part: u8, stats: &BenchStats) -> Self {
+        let nanos = |x: Duration| x.as_nanos() as u64;
+        Self {
+            part,
+            nanos: nanos(stats.median),
+            samples: stats.samples as u64,
+            min_nanos: nanos(stats.min),
+            median_nanos: nanos(stats.median),
+            mean_nanos: nanos(stats.mean),
+            p95_nanos: nanos(stats.p95),
+            max_nanos: nanos(stats.max),
+            std_dev_nanos: nanos(stats.std_dev),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayRecord {
+    pub day: u8,
+    pub parts: Vec<PartRecord>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Export {
+    pub revision: Option<String>,
+    pub host: Host,
+    /// Seconds since the unix epoch at the time the export was created.
+    pub timestamp: u64,
+    pub total_nanos: u64,
+    pub days: Vec<DayRecord>,
+}
+
+impl Export {
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn new(timings: &[Timings], total_millis: f64) -> Self {
+        let days = timings
+            .iter()
+            .map(|timing| DayRecord {
+                day: timing.day.into_inner(),
+                parts: [&timing.part_1_stats, &timing.part_2_stats]
+                    .iter()
+                    .zip(1..)
+                    .filter_map(|(stats, part)| stats.map(|x| PartRecord::new(part, &x)))
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            revision: git_revision(),
+            host: Host::current(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            total_nanos: (total_millis * 1_000_000_f64).round() as u64,
+            days,
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("export is always serializable")
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        serde_json::from_str(s).map_err(|e| Error::Parser(e.to_string()))
+    }
+
+    /// Flattens the export into one row per day and part. Run-level fields are repeated on every row.
+    #[must_use]
+    pub fn to_csv(&self) -> String {
+        let mut csv = CSV_HEADER.join(",");
+        csv.push('\n');
+
+        for day in &self.days {
+            for part in &day.parts {
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                    day.day,
+                    part.part,
+                    part.nanos,
+                    part.samples,
+                    part.min_nanos,
+                    part.median_nanos,
+                    part.mean_nanos,
+                    part.p95_nanos,
+                    part.max_nanos,
+                    part.std_dev_nanos,
+                    csv_field(self.revision.as_deref().unwrap_or_default()),
+                    csv_field(self.host.hostname.as_deref().unwrap_or_default()),
+                    csv_field(&self.host.os),
+                    csv_field(&self.host.arch),
+                    self.host.cpus.map(|x| x.to_string()).unwrap_or_default(),
+                    self.timestamp,
+                );
+            }
+        }
+
+        csv
+    }
+
+    pub fn from_csv(s: &str) -> Result<Self, Error> {
+        let mut lines = s.lines().filter(|l| !l.trim().is_empty());
+
+        let header: Vec<&str> = lines
+            .next()
+            .ok_or_else(|| Error::Parser("missing CSV header".into()))?
+            .split(',')
+            .collect();
+
+        if header != CSV_HEADER {
+            return Err(Error::Parser("unexpected CSV header".into()));
+        }
+
+        let mut export = Self::default();
+
+        for (index, line) in lines.enumerate() {
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields.len() != CSV_HEADER.len() {
+                return Err(Error::Parser(format!(
+                    "row {} has {} fields, expected {}",
+                    index + 1,
+                    fields.len(),
+                    CSV_HEADER.len()
+                )));
+            }
+
+            let num = |i: usize| -> Result<u64, Error> {
+                fields[i].parse().map_err(|_| {
+                    Error::Parser(format!(
+                        "row {}: invalid value for `{}`",
+                        index + 1,
+                        CSV_HEADER[i]
+                    ))
+                })
+            };
+
+            #[allow(clippy::cast_possible_truncation)]
+            let (day, part) = (num(0)? as u8, num(1)? as u8);
+
+            let record = PartRecord {
+                part,
+                nanos: num(2)?,
+                samples: num(3)?,
+                min_nanos: num(4)?,
+                median_nanos: num(5)?,
+                mean_nanos: num(6)?,
+                p95_nanos: num(7)?,
+                max_nanos: num(8)?,
+                std_dev_nanos: num(9)?,
+            };
+
+            export.total_nanos += record.nanos;
+
+            match export.days.iter_mut().find(|x| x.day == day) {
+                Some(day_record) => day_record.parts.push(record),
+                None => export.days.push(DayRecord {
+                    day,
+                    parts: vec![record],
+                }),
+            }
+
+            let text = |i: usize| Some(fields[i].to_string()).filter(|x| !x.is_empty());
+            export.revision = text(10);
+            export.host = Host {
+                hostname: text(11),
+                os: fields[12].into(),
+                arch: fields[13].into(),
+                cpus: fields[14].parse().ok(),
+            };
+            export.timestamp = num(15)?;
+        }
+
+        Ok(export)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        let content = match Format::from_path(path) {
+            Format::Json => self.to_json(),
+            Format::Csv => self.to_csv(),
+        };
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> Result<Self, Error> {
+        let content = fs::read_to_string(path)?;
+        match Format::from_path(path) {
+            Format::Json => Self::from_json(&content),
+            Format::Csv => Self::from_csv(&content),
+        }
+    }
+}
+
+const CSV_HEADER: [&str; 16] = [
+    "day",
+    "part",
+    "nanos",
+    "samples",
+    "min_nanos",
+    "median_nanos",
+    "mean_nanos",
+    "p95_nanos",
+    "max_nanos",
+    "std_dev_nanos",
+    "revision",
+    "hostname",
+    "os",
+    "arch",
+    "cpus",
+    "timestamp",
+];
+
+/// Commas would break the (deliberately simple) CSV format, so they are replaced.
+fn csv_field(s: &str) -> String {
+    s.replace(',', ";")
+}
+
+/// The `git describe` output of the working tree, e.g. `abc1234-dirty`.
+pub(crate) fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|x| !x.is_empty())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Export, Host};
+    use crate::day;
+    use crate::template::{readme_benchmarks::Timings, runner::BenchStats};
+
+    fn get_mock_export() -> Export {
+        let stats = |ms: u64| BenchStats {
+            samples: 10,
+            min: Duration::from_millis(ms - 1),
+            median: Duration::from_millis(ms),
+            mean: Duration::from_millis(ms),
+            p95: Duration::from_millis(ms + 1),
+            max: Duration::from_millis(ms + 2),
+            std_dev: Duration::from_micros(500),
+        };
+
+        let timings = vec![
+            Timings {
+                day: day!(1),
+                part_1: Some("10ms".into()),
+                part_2: Some("20ms".into()),
+                part_1_stats: Some(stats(10)),
+                part_2_stats: Some(stats(20)),
+                part_1_memory: None,
+                part_2_memory: None,
+                total_nanos: 3e+7,
+            },
+            Timings {
+                day: day!(3),
+                part_1: Some("30ms".into()),
+                part_2: None,
+                part_1_stats: Some(stats(30)),
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
+                total_nanos: 3e+7,
+            },
+        ];
+
+        let mut export = Export::new(&timings, 60.0);
+        export.revision = Some("abc1234".into());
+        export.host = Host {
+            hostname: Some("elf".into()),
+            os: "linux".into(),
+            arch: "x86_64".into(),
+            cpus: Some(8),
+        };
+        export.timestamp = 1_701_388_800;
+        export
+    }
+
+    #[test]
+    fn collects_parts_with_stats() {
+        let export = get_mock_export();
+        assert_eq!(export.total_nanos, 60_000_000);
+        assert_eq!(export.days.len(), 2);
+        assert_eq!(export.days[0].parts.len(), 2);
+        assert_eq!(export.days[1].parts.len(), 1);
+        assert_eq!(export.days[1].parts[0].nanos, 30_000_000);
+        assert_eq!(export.days[1].parts[0].p95_nanos, 31_000_000);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let export = get_mock_export();
+        assert_eq!(Export::from_json(&export.to_json()).unwrap(), export);
+    }
+
+    #[test]
+    fn round_trips_csv() {
+        let export = get_mock_export();
+        let csv = export.to_csv();
+        assert_eq!(csv.lines().count(), 4);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "1,1,10000000,10,9000000,10000000,10000000,11000000,12000000,500000,abc1234,elf,linux,x86_64,8,1701388800"
+        );
+        assert_eq!(Export::from_csv(&csv).unwrap(), export);
+    }
+}
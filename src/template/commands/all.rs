@@ -1,254 +1,703 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::template::{
+    benchmark_compare::{self, Comparison},
+    benchmark_export::Export,
+    input::{self, InputError},
     readme_benchmarks::{self, Timings},
+    registry::{self, PartFn, Solution},
+    report::{OutputFormat, PartReport},
+    runner::{isolate, panic_message, BenchConfig, BenchStats, PartOutcome, RunConfig},
+    variants::{self, Disagreement, VariantRun},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+use crate::{Day, Event, Year};
+
+pub struct Options {
+    /// The event whose days are run.
+    pub year: Year,
+    /// Marks the timings as release timings, which updates the README benchmarks.
+    /// Days run in-process, so the flag does not change how they are compiled: that is decided
+    /// by the profile of the `cargo all` binary, which the alias always builds with `--release`.
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub bench_config: BenchConfig,
+    /// Writes the collected timings to this file (JSON, or CSV for a `.csv` extension).
+    pub export: Option<PathBuf>,
+    /// Compares the collected timings against an earlier export.
+    pub compare: Option<PathBuf>,
+    /// Slowdown in percent above which a part counts as a regression when comparing.
+    pub regression_threshold: f64,
+    /// Number of days that are solved concurrently. Benchmarks always run one day at a time, the
+    /// CLI rejects `--jobs` together with `--time`.
+    pub jobs: usize,
+    pub format: OutputFormat,
+    /// Time a single part may take before it is reported as timed out.
+    pub part_timeout: Option<Duration>,
+    /// Time both parts of a day may take together before the remaining part is reported as timed out.
+    pub day_timeout: Option<Duration>,
+    /// Number of runs kept in the history section of the README, [`None`] keeps the current size.
+    pub history: Option<usize>,
+}
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            year: Year::from_env(),
+            is_release: false,
+            is_timed: false,
+            bench_config: BenchConfig::default(),
+            export: None,
+            compare: None,
+            regression_threshold: benchmark_compare::DEFAULT_THRESHOLD_PERCENT,
+            jobs: 1,
+            format: OutputFormat::default(),
+            history: None,
+            part_timeout: None,
+            day_timeout: None,
         }
+    }
+}
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
-
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+pub fn handle(options: &Options) {
+    let Options {
+        year,
+        is_release,
+        is_timed,
+        ref bench_config,
+        ..
+    } = *options;
+
+    if !is_timed && (options.export.is_some() || options.compare.is_some()) {
+        eprintln!("Warning: --export and --compare require --time, they will be ignored.");
+    }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
-        }
+    // load the baseline up front so a typo in the path does not cost a full benchmark run.
+    let baseline = options.compare.as_ref().filter(|_| is_timed).map(|path| {
+        Export::read(path).unwrap_or_else(|e| {
+            eprintln!("Failed to load baseline \"{}\": {e}", path.display());
+            process::exit(1);
+        })
     });
 
+    // concurrent runs compete for cores and caches, which would skew the numbers.
+    let jobs = if is_timed { 1 } else { options.jobs.max(1) };
+
+    let days: Vec<Day> = Event::for_year(year).days().collect();
+    let limits = Limits {
+        part: options.part_timeout,
+        day: options.day_timeout,
+    };
+    let bench_config = is_timed.then_some(*bench_config);
+
+    let mut report = Report::new(year, options.format);
+
+    if jobs > 1 {
+        run_parallel(&days, jobs, bench_config, limits, &mut report);
+    } else {
+        run_sequential(&days, bench_config, limits, &mut report);
+    }
+
+    let Report {
+        timings, summary, ..
+    } = report;
+
+    summary.print();
+
+    // JSON output is reserved for the part reports, everything else goes to stderr.
+    let note = |message: String| match options.format {
+        OutputFormat::Text => println!("{message}"),
+        OutputFormat::Json => eprintln!("{message}"),
+    };
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        note(format!(
+            "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+        ));
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+        let export = Export::new(&timings, total_millis);
+
+        if let Some(path) = &options.export {
+            match export.write(path) {
+                Ok(()) => note(format!(
+                    "Successfully exported benchmarks to \"{}\".",
+                    path.display()
+                )),
+                Err(e) => eprintln!("Failed to export benchmarks: {e}"),
+            }
+        }
+
+        // the README documents the default year, other events only print their timings.
+        if is_release && year.is_default() {
+            match readme_benchmarks::update(timings, total_millis, options.history) {
+                Ok(()) => note("Successfully updated README with benchmarks.".into()),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
         }
+
+        if let Some(baseline) = baseline {
+            let comparison = Comparison::new(&baseline, &export, options.regression_threshold);
+
+            note(format!("\n{ANSI_BOLD}Comparison with baseline{ANSI_RESET}"));
+            note(comparison.render().trim_end().to_string());
+
+            let missing = comparison.missing().count();
+            if missing > 0 {
+                eprintln!("Found {missing} part(s) of the baseline missing from this run.");
+            }
+
+            if comparison.has_regressions() {
+                eprintln!(
+                    "Found {} regression(s) above {:.1}%.",
+                    comparison.regressions().count(),
+                    options.regression_threshold
+                );
+            }
+
+            if missing > 0 || comparison.has_regressions() {
+                process::exit(1);
+            }
+        }
+    }
+
+    if !summary.errored.is_empty() || !summary.disagreements.is_empty() {
+        process::exit(1);
     }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+/// Timeouts of a run, see [`Options::part_timeout`] and [`Options::day_timeout`].
+#[derive(Clone, Copy, Debug, Default)]
+struct Limits {
+    part: Option<Duration>,
+    day: Option<Duration>,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+/// The outcome of the parts of a further variant of a day, see [`registry::variants`].
+struct VariantOutcome {
+    variant: &'static Solution,
+    parts: Vec<PartOutcome>,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+#[allow(clippy::large_enum_variant)]
+enum DayOutcome {
+    /// The day has not been scaffolded yet.
+    NotSolved,
+    InputError(InputError),
+    /// The parts of the main solution, followed by the outcomes of the other variants of the day.
+    Solved(Vec<PartOutcome>, Vec<VariantOutcome>),
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+fn run_day(year: Year, day: Day, config: &RunConfig, limits: Limits) -> DayOutcome {
+    let variants = registry::variants(year, day);
+    let Some((solution, others)) = variants.split_first() else {
+        return DayOutcome::NotSolved;
+    };
+
+    let input: Arc<str> = match input::load(year, day, solution.shape, solution.pattern) {
+        Ok(input) => input.into(),
+        Err(e) => return DayOutcome::InputError(e),
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    let parts = run_variant(solution, &input, config, limits);
+
+    // only the main solution prints live, the variants are compared with it once they ran.
+    let quiet = RunConfig {
+        quiet: true,
+        ..*config
+    };
+    let others = others
+        .iter()
+        .map(|variant| VariantOutcome {
+            variant,
+            parts: run_variant(variant, &input, &quiet, limits),
+        })
+        .collect();
+
+    DayOutcome::Solved(parts, others)
+}
+
+/// Runs the parts of a variant, every variant gets the full time of a day.
+fn run_variant(
+    solution: &Solution,
+    input: &Arc<str>,
+    config: &RunConfig,
+    limits: Limits,
+) -> Vec<PartOutcome> {
+    let started = Instant::now();
+    let timeout = || {
+        let day_left = limits.day.map(|x| x.saturating_sub(started.elapsed()));
+        match (limits.part, day_left) {
+            (Some(part), Some(day_left)) => Some(part.min(day_left)),
+            (part, day_left) => part.or(day_left),
         }
+    };
+
+    [(1, solution.part_one), (2, solution.part_two)]
+        .into_iter()
+        .take(usize::from(solution.parts()))
+        .map(|(part, func)| run_part(func, part, input, config, timeout()))
+        .collect()
+}
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+/// Runs a part once on its own thread, so a panic or a timeout only affects this part.
+/// Parts that answered in time are benched afterwards if the config asks for it, isolated as well
+/// and within what is left of `timeout`.
+fn run_part(
+    func: PartFn,
+    part: u8,
+    input: &Arc<str>,
+    config: &RunConfig,
+    timeout: Option<Duration>,
+) -> PartOutcome {
+    let probe = RunConfig {
+        bench: None,
+        quiet: true,
+    };
 
-        if is_release {
-            args.push("--release");
+    let started = Instant::now();
+    let outcome = match isolate(func, Arc::clone(input), probe, timeout) {
+        PartOutcome::Finished(run) if run.answer.is_some() && config.bench.is_some() => {
+            // benching runs the part many more times, which can still exceed the budget.
+            let remaining = timeout.map(|x| x.saturating_sub(started.elapsed()));
+            match isolate(func, Arc::clone(input), *config, remaining) {
+                // the bench printed the part itself.
+                outcome @ PartOutcome::Finished(_) => return outcome,
+                PartOutcome::TimedOut(_) => PartOutcome::TimedOut(timeout.unwrap_or_default()),
+                outcome @ PartOutcome::Panicked(_) => outcome,
+            }
         }
+        outcome => outcome,
+    };
+
+    if !config.quiet {
+        outcome.print(part);
+    }
+    outcome
+}
+
+/// Counts the parts that did not produce an answer, by reason, and the variants that disagree.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Summary {
+    /// Parts that returned an error, or could not run because the input of their day is invalid.
+    errored: Vec<(Day, u8)>,
+    timed_out: Vec<(Day, u8)>,
+    panicked: Vec<(Day, u8)>,
+    not_solved: usize,
+    disagreements: Vec<(Day, Disagreement)>,
+}
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+impl Summary {
+    fn add(&mut self, day: Day, parts: u8, outcome: &DayOutcome) {
+        match outcome {
+            DayOutcome::NotSolved => self.not_solved += usize::from(parts),
+            DayOutcome::InputError(_) => self.errored.extend((1..=parts).map(|x| (day, x))),
+            DayOutcome::Solved(parts, _) => {
+                for (part, outcome) in (1..).zip(parts) {
+                    match outcome {
+                        PartOutcome::Finished(run) if run.error.is_some() => {
+                            self.errored.push((day, part));
+                        }
+                        PartOutcome::Finished(run) if run.answer.is_none() => self.not_solved += 1,
+                        PartOutcome::Finished(_) => {}
+                        PartOutcome::TimedOut(_) => self.timed_out.push((day, part)),
+                        PartOutcome::Panicked(_) => self.panicked.push((day, part)),
+                    }
+                }
+            }
         }
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    fn print(&self) {
+        let list = |parts: &[(Day, u8)]| {
+            parts
+                .iter()
+                .map(|(day, part)| format!("day {day} part {part}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        if !self.errored.is_empty() {
+            eprintln!("\nFailed: {}.", list(&self.errored));
+        }
+        if !self.timed_out.is_empty() {
+            eprintln!("\nTimed out: {}.", list(&self.timed_out));
+            eprintln!("Note: timed out parts keep running in the background until `all` exits.");
+        }
+        if !self.panicked.is_empty() {
+            eprintln!("\nPanicked: {}.", list(&self.panicked));
+        }
+        if self.not_solved > 0 {
+            eprintln!("\n{} part(s) not solved.", self.not_solved);
+        }
+        if !self.disagreements.is_empty() {
+            eprintln!("\nVariants disagree:");
+            for (day, disagreement) in &self.disagreements {
+                eprintln!("  day {day}: {disagreement}");
+            }
+        }
+    }
+}
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+/// Prints the outcome of every day in the selected format, and collects the timings and the summary.
+struct Report {
+    year: Year,
+    event: Event,
+    format: OutputFormat,
+    timings: Vec<Timings>,
+    summary: Summary,
+}
 
-        let mut output = vec![];
+impl Report {
+    fn new(year: Year, format: OutputFormat) -> Self {
+        Self {
+            year,
+            event: Event::for_year(year),
+            format,
+            timings: vec![],
+            summary: Summary::default(),
+        }
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    /// Whether parts print their results themselves while they run, see [`RunConfig::quiet`].
+    fn is_live(&self, sequential: bool) -> bool {
+        sequential && self.format == OutputFormat::Text
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    fn start_day(&self, index: usize, day: Day) {
+        if self.format != OutputFormat::Text {
+            return;
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        if index > 0 {
+            println!();
+        }
 
-        Ok(output)
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Prints the parts of an outcome that were not already printed live, and collects its timings.
+    fn add(&mut self, day: Day, outcome: &DayOutcome, is_live: bool) {
+        match (outcome, self.format) {
+            (DayOutcome::NotSolved, OutputFormat::Text) => println!("Not solved."),
+            (DayOutcome::NotSolved, OutputFormat::Json) => {}
+            (DayOutcome::InputError(e), format) => {
+                eprintln!("Invalid input for day {day}: {e}");
+                if format == OutputFormat::Json {
+                    for part in 1..=self.event.parts(day) {
+                        PartReport::failed(self.year, day, part, format!("invalid input: {e}"))
+                            .print();
+                    }
+                }
+            }
+            (DayOutcome::Solved(parts, others), format) => {
+                for (part, outcome) in (1..).zip(parts) {
+                    match format {
+                        OutputFormat::Text if !is_live => outcome.print(part),
+                        OutputFormat::Text => {}
+                        OutputFormat::Json => outcome.report(self.year, day, part).print(),
+                    }
+                }
+                self.timings.push(collect_timings(day, parts));
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+                if !others.is_empty() {
+                    self.add_variants(day, parts, others);
                 }
+            }
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        self.summary.add(day, self.event.parts(day), outcome);
+    }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+    /// Prints the variants of a day next to the main solution and collects their disagreements.
+    fn add_variants(&mut self, day: Day, main: &[PartOutcome], others: &[VariantOutcome]) {
+        let variant_run = |name: &str, variant: Option<&str>, parts: &[PartOutcome]| VariantRun {
+            name: name.into(),
+            parts: (1..)
+                .zip(parts)
+                .map(|(part, outcome)| outcome.report(self.year, day, part).with_variant(variant))
+                .collect(),
+        };
+
+        let mut runs = vec![variant_run("main", None, main)];
+        runs.extend(
+            others
+                .iter()
+                .map(|x| variant_run(x.variant.name(), x.variant.variant, &x.parts)),
+        );
+
+        let disagreements = variants::disagreements(&runs);
+
+        match self.format {
+            OutputFormat::Text => {
+                println!("\n{}", variants::render(&runs));
+                if disagreements.is_empty() {
+                    println!("{ANSI_ITALIC}All variants agree.{ANSI_RESET}");
+                }
+                for disagreement in &disagreements {
+                    println!("✖ {disagreement}");
                 }
+            }
+            OutputFormat::Json => {
+                for report in runs.iter().skip(1).flat_map(|x| &x.parts) {
+                    report.print();
+                }
+            }
+        }
+
+        self.summary
+            .disagreements
+            .extend(disagreements.into_iter().map(|x| (day, x)));
+    }
+}
+
+/// Runs the days one after another, printing results as they are produced.
+fn run_sequential(
+    days: &[Day],
+    bench_config: Option<BenchConfig>,
+    limits: Limits,
+    report: &mut Report,
+) {
+    let is_live = report.is_live(true);
+    let config = RunConfig {
+        bench: bench_config,
+        quiet: !is_live,
+    };
 
-                timings.total_nanos += nanos;
+    for (index, &day) in days.iter().enumerate() {
+        report.start_day(index, day);
+        let outcome = run_day(report.year, day, &config, limits);
+        report.add(day, &outcome, is_live);
+    }
+}
+
+/// Runs up to `jobs` days concurrently. Results are buffered and printed in day order,
+/// as soon as every earlier day has been printed.
+fn run_parallel(
+    days: &[Day],
+    jobs: usize,
+    bench_config: Option<BenchConfig>,
+    limits: Limits,
+    report: &mut Report,
+) {
+    let config = RunConfig {
+        bench: bench_config,
+        quiet: true,
+    };
+
+    let year = report.year;
+    let next_day = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel::<(usize, DayOutcome)>();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_day = &next_day;
+            let config = &config;
+
+            scope.spawn(move || loop {
+                let index = next_day.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+                // parts are isolated already, this catches panics of the day around them, e.g.
+                // in a registry lookup, so the scope does not re-panic and lose the report.
+                let outcome =
+                    panic::catch_unwind(AssertUnwindSafe(|| run_day(year, day, config, limits)))
+                        .unwrap_or_else(|payload| {
+                            let message = panic_message(payload.as_ref());
+                            let parts = Event::for_year(year).parts(day);
+                            DayOutcome::Solved(
+                                vec![PartOutcome::Panicked(message); usize::from(parts)],
+                                vec![],
+                            )
+                        });
+
+                if tx.send((index, outcome)).is_err() {
+                    break;
+                }
             });
+        }
+
+        // the receiver loop below ends once every worker dropped its sender.
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_print = 0;
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+        for (index, outcome) in rx {
+            pending.insert(index, outcome);
+
+            while let Some(outcome) = pending.remove(&next_print) {
+                report.start_day(next_print, days[next_print]);
+                report.add(days[next_print], &outcome, false);
+                next_print += 1;
+            }
         }
+    });
+}
+
+/// Collects the timings of parts that produced an answer, mirroring what is printed for them.
+/// Parts that timed out or panicked are marked as such in the table, parts the day does not have
+/// are left empty.
+fn collect_timings(day: Day, parts: &[PartOutcome]) -> Timings {
+    let timed = |outcome: Option<&PartOutcome>| match outcome {
+        Some(PartOutcome::Finished(run)) => run.answer.as_ref().map(|_| run.stats),
+        _ => None,
+    };
+
+    let cell = |outcome: Option<&PartOutcome>, stats: Option<BenchStats>| {
+        stats
+            .map(|x| format!("{:.1?}", x.median))
+            .or_else(|| outcome?.marker().map(ToString::to_string))
+    };
+
+    let memory = |outcome: Option<&PartOutcome>| match outcome {
+        Some(PartOutcome::Finished(run)) => run.answer.as_ref().and(run.memory),
+        _ => None,
+    };
+
+    let (part_1, part_2) = (parts.first(), parts.get(1));
+    let part_1_stats = timed(part_1);
+    let part_2_stats = timed(part_2);
+
+    #[allow(clippy::cast_precision_loss)]
+    let total_nanos = [part_1_stats, part_2_stats]
+        .iter()
+        .flatten()
+        .map(|x| x.median.as_nanos() as f64)
+        .sum();
+
+    Timings {
+        day,
+        part_1: cell(part_1, part_1_stats),
+        part_2: cell(part_2, part_2_stats),
+        part_1_stats,
+        part_2_stats,
+        part_1_memory: memory(part_1),
+        part_2_memory: memory(part_2),
+        total_nanos,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::{collect_timings, run_part, DayOutcome, PartOutcome, Summary};
+    use crate::day;
+    use crate::template::input::InputError;
+    use crate::template::runner::{BenchConfig, BenchStats, PartRun, RunConfig};
+
+    fn finished(answer: Option<&str>, stats: BenchStats) -> PartOutcome {
+        PartOutcome::Finished(PartRun {
+            answer: answer.map(Into::into),
+            error: None,
+            stats,
+            memory: None,
+        })
+    }
+
+    #[test]
+    fn collects_answered_parts() {
+        let stats = BenchStats {
+            samples: 10,
+            median: Duration::from_micros(74130),
+            ..BenchStats::default()
+        };
+        let res = collect_timings(
+            day!(1),
+            &[finished(Some("10"), stats), finished(None, stats)],
+        );
+        assert_eq!(res.total_nanos, 74_130_000_f64);
+        assert_eq!(res.part_1.unwrap(), "74.1ms");
+        assert_eq!(res.part_1_stats, Some(stats));
+        assert!(res.part_2.is_none());
+        assert!(res.part_2_stats.is_none());
+
+        // the last day of an event has a single part.
+        let res = collect_timings(day!(25), &[finished(Some("10"), stats)]);
+        assert_eq!(res.total_nanos, 74_130_000_f64);
+        assert!(res.part_2.is_none());
+    }
+
+    #[test]
+    fn marks_failed_parts() {
+        let res = collect_timings(
+            day!(1),
+            &[
+                PartOutcome::TimedOut(Duration::from_secs(1)),
+                PartOutcome::Panicked("oops".into()),
+            ],
+        );
+        assert_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.unwrap(), "timed out");
+        assert_eq!(res.part_2.unwrap(), "panicked");
+        assert!(res.part_1_stats.is_none());
+    }
+
+    #[test]
+    fn counts_parts_that_returned_errors() {
+        let errored = PartOutcome::Finished(PartRun {
+            answer: None,
+            error: Some("invalid input".into()),
+            stats: BenchStats::default(),
+            memory: None,
+        });
+
+        let mut summary = Summary::default();
+        summary.add(
+            day!(1),
+            2,
+            &DayOutcome::Solved(vec![finished(None, BenchStats::default()), errored], vec![]),
+        );
+        summary.add(day!(2), 2, &DayOutcome::InputError(InputError::Empty));
+
+        assert_eq!(summary.errored, [(day!(1), 2), (day!(2), 1), (day!(2), 2)]);
+        assert_eq!(summary.not_solved, 1);
+    }
+
+    #[test]
+    fn limits_benches_to_the_budget() {
+        let input: Arc<str> = "input".into();
+        let config = RunConfig {
+            bench: Some(BenchConfig::default()),
+            quiet: true,
+        };
+
+        // answers right away once, but takes too long when it is benched.
+        let slow_bench = |_: &str, config: &RunConfig| {
+            if config.bench.is_some() {
+                std::thread::sleep(Duration::from_secs(60));
+            }
+            PartRun {
+                answer: Some("1".into()),
+                error: None,
+                stats: BenchStats::default(),
+                memory: None,
+            }
+        };
+
+        let timeout = Some(Duration::from_millis(50));
+        assert_eq!(
+            run_part(slow_bench, 1, &input, &config, timeout),
+            PartOutcome::TimedOut(Duration::from_millis(50))
+        );
     }
 }
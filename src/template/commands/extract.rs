@@ -0,0 +1,61 @@
+use std::{fs, path::Path, process};
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::examples::{answers_path, example_path, extract};
+use crate::{Day, Year};
+
+/// Whether a file is missing or empty, like the example files created by `scaffold`.
+fn is_blank(path: &Path) -> bool {
+    fs::read_to_string(path).map_or(true, |x| x.trim().is_empty())
+}
+
+pub fn handle(year: Year, day: Day, force: bool) {
+    let puzzle_path = get_puzzle_path(year, day);
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{}\", download the puzzle first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let examples = extract(&puzzle);
+
+    if examples.is_empty() {
+        eprintln!("Could not find an example in the puzzle description.");
+        process::exit(1);
+    }
+
+    for (index, example) in examples.iter().enumerate() {
+        let path = example_path(year, day, index + 1);
+        let answers = answers_path(&path);
+
+        if !force && !is_blank(&path) {
+            println!(
+                "Skipped \"{}\", it already exists. Pass --force to overwrite it.",
+                path.display()
+            );
+            continue;
+        }
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&path, &example.input))
+            .and_then(|()| fs::write(&answers, example.answers.to_string()));
+
+        match result {
+            Ok(()) => {
+                println!("Wrote example to \"{}\"", path.display());
+                for line in example.answers.to_string().lines() {
+                    println!("  {line}");
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to write example \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        }
+    }
+}
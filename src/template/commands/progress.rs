@@ -0,0 +1,31 @@
+use std::{path::Path, process};
+
+use crate::template::progress::Calendar;
+use crate::template::readme_benchmarks::Error;
+use crate::template::readme_progress;
+use crate::template::submissions::{self, Ledger};
+use crate::Year;
+
+pub fn handle(year: Year) {
+    let ledger = Ledger::load(Path::new(submissions::LEDGER_PATH)).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    let calendar = Calendar::collect(year, &ledger);
+    println!("{}", calendar.render());
+
+    // the README documents the default year, like the benchmarks.
+    if !year.is_default() {
+        return;
+    }
+
+    match readme_progress::update(&calendar) {
+        Ok(()) => println!("\nSuccessfully updated README with progress."),
+        Err(Error::Parser(_)) => println!(
+            "\nAdd two `{}` markers to the README to keep a progress section there.",
+            readme_progress::MARKER
+        ),
+        Err(e) => eprintln!("\nFailed to update README with progress: {e}"),
+    }
+}
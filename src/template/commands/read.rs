@@ -1,16 +1,27 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::{aoc_client, markdown};
+use crate::{Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Prints the puzzle description of a day. The stored description is read offline, it is only
+/// fetched when it was not downloaded yet or when `refresh` is set, e.g. to pick up part two.
+pub fn handle(year: Year, day: Day, refresh: bool) {
+    let stored = fs::read_to_string(aoc_client::get_puzzle_path(year, day)).ok();
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let puzzle = match stored {
+        Some(puzzle) if !refresh => puzzle,
+        _ => match aoc_client::read(year, day) {
+            Ok(puzzle) => puzzle,
+            Err(e) => {
+                eprintln!("failed to read day {day} of {year}: {e}");
+                process::exit(1);
+            }
+        },
     };
+
+    let lines = markdown::render(&puzzle, markdown::terminal_width());
+    if let Err(e) = markdown::page(&lines) {
+        eprintln!("failed to print day {day} of {year}: {e}");
+        process::exit(1);
+    }
 }
@@ -1,10 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{bin_path, data_dir};
+use crate::{Day, Year};
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
@@ -22,14 +24,12 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::examples::check_part(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        advent_of_code::template::examples::check_part(DAY, 2, part_two);
     }
 }
 "#;
@@ -39,13 +39,39 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// The module template, with the event named explicitly for years other than the default one.
+fn module_contents(year: Year, day: Day) -> String {
+    let contents = if year.is_default() {
+        MODULE_TEMPLATE.to_string()
+    } else {
+        MODULE_TEMPLATE
+            .replace(
+                "solution!(DAY_NUMBER)",
+                "solution!(YEAR_NUMBER, DAY_NUMBER)",
+            )
+            .replace("check_part(DAY", "check_part_for_year(YEAR, DAY")
+    };
+
+    contents
+        .replace("YEAR_NUMBER", &year.to_string())
+        .replace("DAY_NUMBER", &day.into_inner().to_string())
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(year: Year, day: Day) {
+    let data_dir = data_dir(year);
+    let input_path = format!("{}/inputs/{day}.txt", data_dir.display());
+    let example_path = format!("{}/examples/{day}.txt", data_dir.display());
+    let module_path = bin_path(year, day);
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -55,11 +81,7 @@ pub fn handle(day: Day) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module_contents(year, day).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -90,5 +112,12 @@ pub fn handle(day: Day) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    if year.is_default() {
+        println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            day, year
+        );
+    }
 }
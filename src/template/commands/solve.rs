@@ -1,27 +1,96 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::Day;
+use crate::template::bin_name;
+use crate::template::commands::watch;
+use crate::template::input::InputSource;
+use crate::template::memory;
+use crate::template::registry::{self, Solution};
+use crate::template::report::{OutputFormat, PartReport};
+use crate::template::runner::BenchConfig;
+use crate::template::variants::{self, VariantRun};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub struct Options {
+    pub year: Year,
+    pub day: Day,
+    pub release: bool,
+    pub time: bool,
+    pub submit_part: Option<u8>,
+    pub bench_config: BenchConfig,
+    pub input: InputSource,
+    /// Re-runs the day whenever its source, input or examples change.
+    pub watch: bool,
+    /// Runs the tests of the day after solving it.
+    pub test: bool,
+    pub format: OutputFormat,
+}
+
+pub fn handle(options: &Options) {
+    if options.watch {
+        watch::handle(options);
+        return;
+    }
+
+    let variants = registry::variants(options.year, options.day);
+
+    // answers are only ever submitted for the main solution.
+    let agree = if variants.len() > 1 && options.submit_part.is_none() {
+        run_variants(options, &variants)
+    } else {
+        run_solution(options);
+        true
+    };
+
+    if options.test {
+        run_tests(options);
+    }
+
+    if !agree {
+        process::exit(1);
+    }
+}
+
+/// Arguments of the `cargo` invocation that builds and runs the solution binary.
+#[must_use]
+pub fn solve_args(options: &Options) -> Vec<String> {
+    run_args(options, bin_name(options.year, options.day), options.format)
+}
 
-    if release {
+pub(crate) fn run_args(options: &Options, bin_name: String, format: OutputFormat) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), bin_name];
+
+    if options.release {
         cmd_args.push("--release".to_string());
     }
 
+    // measure the solution like this command is measured, i.e. `cargo run --features memory -- solve`.
+    if memory::is_enabled() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("memory".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
+    cmd_args.extend(options.input.to_args());
+    cmd_args.extend(format.to_args());
+
+    if options.time {
         cmd_args.push("--time".to_string());
+        cmd_args.extend(options.bench_config.to_args());
     }
 
+    cmd_args
+}
+
+fn run_solution(options: &Options) {
     let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+        .args(solve_args(options))
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
@@ -29,3 +98,92 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Runs every variant of the day, prints them side by side and returns whether they agree.
+/// Variants run with `--format json`, so their results can be read back and compared.
+fn run_variants(options: &Options, variants: &[&Solution]) -> bool {
+    let Options { year, day, .. } = *options;
+    let mut runs = vec![];
+
+    for variant in variants {
+        if options.format == OutputFormat::Text {
+            println!("{ANSI_ITALIC}Running {}…{ANSI_RESET}", variant.name());
+        }
+
+        let output = Command::new("cargo")
+            .args(run_args(options, variant.bin_name(), OutputFormat::Json))
+            .stderr(Stdio::inherit())
+            .output()
+            .unwrap();
+
+        let reports: Vec<PartReport> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        // a part that panicked takes the process down with it, and with it every later part.
+        let parts = (1..=variant.parts())
+            .map(|part| {
+                reports
+                    .iter()
+                    .find(|x| x.part == part)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        PartReport::failed(year, day, part, "the solution exited without a result")
+                    })
+                    .with_variant(variant.variant)
+            })
+            .collect();
+
+        runs.push(VariantRun {
+            name: variant.name().into(),
+            parts,
+        });
+    }
+
+    let disagreements = variants::disagreements(&runs);
+
+    match options.format {
+        OutputFormat::Text => {
+            println!("\n{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("{}", variants::render(&runs));
+
+            if disagreements.is_empty() {
+                println!("{ANSI_ITALIC}All variants agree.{ANSI_RESET}");
+            }
+        }
+        OutputFormat::Json => {
+            for report in runs.iter().flat_map(|x| &x.parts) {
+                report.print();
+            }
+        }
+    }
+
+    for disagreement in &disagreements {
+        eprintln!("✖ {disagreement}");
+    }
+
+    disagreements.is_empty()
+}
+
+/// Runs the tests of the solution binary, returns whether they passed.
+pub fn run_tests(options: &Options) -> bool {
+    let mut cmd_args = vec![
+        "test".to_string(),
+        "--bin".to_string(),
+        bin_name(options.year, options.day),
+    ];
+
+    if options.release {
+        cmd_args.push("--release".to_string());
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().is_ok_and(|x| x.success())
+}
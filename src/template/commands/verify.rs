@@ -0,0 +1,210 @@
+use std::{process, sync::Arc, time::Duration};
+
+use crate::template::{
+    answers::{self, Answers},
+    input, registry,
+    runner::{isolate, PartOutcome, RunConfig},
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, Event, Year};
+
+/// Time a part may take before it is reported as an error, so one hanging day does not stall
+/// the whole verification.
+const PART_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// The part produced an answer, but the manifest has none to compare with.
+    Unknown(String),
+    /// Like [`Status::Unknown`], the answer was added to the manifest.
+    Recorded,
+    /// The part returned an error, e.g. because the input could not be parsed.
+    /// Parts that panicked or timed out are errors as well.
+    Error(String),
+    /// The manifest has an answer, but the part no longer produces one.
+    Missing(String),
+    /// The part did not produce an answer.
+    NotSolved,
+}
+
+impl Status {
+    fn cell(&self) -> &'static str {
+        match self {
+            Status::Pass => "✔ pass",
+            Status::Fail { .. } => "✖ fail",
+            Status::Unknown(_) => "? unknown",
+            Status::Recorded => "+ recorded",
+            Status::Error(_) => "✖ error",
+            Status::Missing(_) => "✖ missing",
+            Status::NotSolved => "-",
+        }
+    }
+}
+
+/// The statuses of a day that could not be run at all, e.g. because its input is missing.
+fn day_error(parts: u8, message: &str) -> [Status; 2] {
+    [1, 2].map(|part| {
+        if part > parts {
+            Status::NotSolved
+        } else {
+            Status::Error(message.to_string())
+        }
+    })
+}
+
+fn compare(expected: Option<&str>, actual: Option<String>) -> Status {
+    match (expected, actual) {
+        (Some(expected), None) => Status::Missing(expected.to_string()),
+        (None, None) => Status::NotSolved,
+        (None, Some(actual)) => Status::Unknown(actual),
+        (Some(expected), Some(actual)) if expected == actual.trim() => Status::Pass,
+        (Some(expected), Some(actual)) => Status::Fail {
+            expected: expected.to_string(),
+            actual,
+        },
+    }
+}
+
+/// Runs every scaffolded day against its real input and compares the answers with the manifest
+/// at `data/answers`. With `record`, answers of parts that are not in the manifest yet are added.
+pub fn handle(year: Year, record: bool) {
+    let config = RunConfig {
+        bench: None,
+        quiet: true,
+    };
+
+    let mut rows: Vec<(Day, [Status; 2])> = vec![];
+    let mut problems: Vec<String> = vec![];
+
+    for day in Event::for_year(year).days() {
+        let Some(solution) = registry::get(year, day) else {
+            continue;
+        };
+
+        let input: Arc<str> = match input::load(year, day, solution.shape, solution.pattern) {
+            Ok(input) => input.into(),
+            Err(e) => {
+                problems.push(format!("Day {day}: invalid input: {e}"));
+                rows.push((day, day_error(solution.parts(), "invalid input")));
+                continue;
+            }
+        };
+
+        let manifest_path = answers::manifest_path(year, day);
+        let mut manifest = match Answers::load(&manifest_path) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                problems.push(format!("Day {day}: {e}"));
+                rows.push((day, day_error(solution.parts(), "invalid manifest")));
+                continue;
+            }
+        };
+
+        let mut statuses = [(1, solution.part_one), (2, solution.part_two)].map(|(part, func)| {
+            if part > solution.parts() {
+                return Status::NotSolved;
+            }
+            match isolate(func, Arc::clone(&input), config, Some(PART_TIMEOUT)) {
+                PartOutcome::Finished(run) => match run.error {
+                    Some(e) => Status::Error(e),
+                    None => compare(manifest.get(part), run.answer),
+                },
+                PartOutcome::TimedOut(timeout) => {
+                    Status::Error(format!("timed out after {timeout:?}"))
+                }
+                PartOutcome::Panicked(message) => Status::Error(format!("panicked: {message}")),
+            }
+        });
+
+        for (part, status) in (1..=2).zip(statuses.iter_mut()) {
+            match status {
+                Status::Fail { expected, actual } => problems.push(format!(
+                    "Day {day} part {part}: expected {expected}, got {actual}"
+                )),
+                Status::Error(e) => problems.push(format!("Day {day} part {part}:\n{e}")),
+                Status::Missing(expected) => problems.push(format!(
+                    "Day {day} part {part}: expected {expected}, got no answer"
+                )),
+                Status::Unknown(actual) if record => {
+                    manifest.set(part, actual.trim());
+                    *status = Status::Recorded;
+                }
+                _ => {}
+            }
+        }
+
+        if statuses.contains(&Status::Recorded) {
+            match manifest.save(&manifest_path) {
+                Ok(()) => println!("Recorded answers to \"{}\".", manifest_path.display()),
+                Err(e) => problems.push(format!("Day {day}: {e}")),
+            }
+        }
+
+        rows.push((day, statuses));
+    }
+
+    println!("{ANSI_BOLD}Verification of {year}{ANSI_RESET}\n");
+    println!("| Day | Part 1 | Part 2 |");
+    println!("| :---: | :---: | :---: |");
+    for (day, [part_1, part_2]) in &rows {
+        println!("| {day} | {} | {} |", part_1.cell(), part_2.cell());
+    }
+
+    let count = |status: fn(&Status) -> bool| {
+        rows.iter()
+            .flat_map(|(_, statuses)| statuses.iter())
+            .filter(|x| status(x))
+            .count()
+    };
+
+    println!(
+        "\n{} passed, {} failed, {} without a recorded answer, {} newly recorded.",
+        count(|x| *x == Status::Pass),
+        count(|x| matches!(
+            x,
+            Status::Fail { .. } | Status::Error(_) | Status::Missing(_)
+        )),
+        count(|x| matches!(x, Status::Unknown(_))),
+        count(|x| *x == Status::Recorded)
+    );
+
+    if !problems.is_empty() {
+        eprintln!();
+        for problem in &problems {
+            eprintln!("{problem}");
+        }
+        process::exit(1);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, day_error, Status};
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(compare(Some("42"), Some("42".into())), Status::Pass);
+        assert_eq!(compare(Some("42"), None), Status::Missing("42".into()));
+        assert_eq!(compare(None, None), Status::NotSolved);
+        assert_eq!(compare(None, Some("7".into())), Status::Unknown("7".into()));
+        assert_eq!(
+            compare(Some("42"), Some("41".into())),
+            Status::Fail {
+                expected: "42".into(),
+                actual: "41".into()
+            }
+        );
+    }
+
+    #[test]
+    fn marks_days_that_can_not_run() {
+        let error = Status::Error("invalid input".into());
+        assert_eq!(day_error(2, "invalid input"), [error.clone(), error]);
+        assert_eq!(day_error(1, "invalid input")[1], Status::NotSolved);
+    }
+}
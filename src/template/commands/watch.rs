@@ -0,0 +1,247 @@
+/// Module that re-runs a day whenever its files change, see `cargo solve DD --watch`.
+/// Files are polled rather than subscribed to, which keeps the template free of platform-specific
+/// dependencies. The answers of every run are compared with the run before it.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{self, BufRead, BufReader},
+    path::PathBuf,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::commands::solve::{self, Options};
+use crate::template::examples::{self, answers_path};
+use crate::template::input::InputSource;
+use crate::template::report::{OutputFormat, PartReport};
+use crate::template::runner::{print_part_run, BenchStats, PartRun};
+use crate::template::{bin_name, bin_path, data_dir, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the watched files, [`None`] for files that do not exist.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// The answers reported by a run, [`None`] for parts without an answer.
+type Answers = BTreeMap<u8, Option<String>>;
+
+pub fn handle(options: &Options) {
+    let mut snapshot = take_snapshot(options);
+    let mut previous: Option<Answers> = None;
+
+    loop {
+        match run(options) {
+            Ok(reports) => {
+                let answers = answers(&reports);
+                if let Some(previous) = &previous {
+                    println!("\n{ANSI_BOLD}Compared to the previous run{ANSI_RESET}");
+                    for line in diff(previous, &answers) {
+                        println!("{line}");
+                    }
+                }
+                previous = Some(answers);
+            }
+            Err(e) => eprintln!("Failed to run the solution: {e}"),
+        }
+
+        if options.test {
+            println!();
+            solve::run_tests(options);
+        }
+
+        println!(
+            "\n{ANSI_ITALIC}Watching {} files for changes, press Ctrl+C to stop.{ANSI_RESET}",
+            snapshot.len()
+        );
+
+        let changed = wait_for_change(options, &mut snapshot);
+        println!(
+            "\n{ANSI_BOLD}Changed:{ANSI_RESET} {}",
+            changed
+                .iter()
+                .map(|x| x.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+/// The source of the day, its input and its examples, including their answers.
+fn watched_paths(options: &Options) -> Vec<PathBuf> {
+    let Options { year, day, .. } = *options;
+
+    let mut paths = vec![
+        PathBuf::from(bin_path(year, day)),
+        data_dir(year).join("inputs").join(format!("{day}.txt")),
+    ];
+
+    if let InputSource::File(path) = &options.input {
+        paths.push(path.clone());
+    }
+
+    // discovering the examples again on every poll also picks up new variants.
+    for example in examples::discover(year, day).unwrap_or_default() {
+        paths.push(answers_path(&example.path));
+        paths.push(example.path);
+    }
+
+    paths
+}
+
+fn take_snapshot(options: &Options) -> Snapshot {
+    watched_paths(options)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|x| x.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Blocks until a watched file changes, returns the changed files.
+fn wait_for_change(options: &Options, snapshot: &mut Snapshot) -> Vec<PathBuf> {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = take_snapshot(options);
+        let changed = changed_paths(snapshot, &current);
+
+        if !changed.is_empty() {
+            // editors often write a file in several steps, give them a moment to finish.
+            thread::sleep(POLL_INTERVAL);
+            *snapshot = take_snapshot(options);
+            return changed;
+        }
+    }
+}
+
+fn changed_paths(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    changed.extend(
+        previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned(),
+    );
+
+    changed
+}
+
+/// Builds and runs the solution with `--format json`, printing the parts as they are reported.
+/// Other output of the solution, e.g. debug prints, is passed through.
+fn run(options: &Options) -> io::Result<Vec<PartReport>> {
+    let mut child = Command::new("cargo")
+        .args(solve::run_args(
+            options,
+            bin_name(options.year, options.day),
+            OutputFormat::Json,
+        ))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let mut reports = vec![];
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            match serde_json::from_str::<PartReport>(&line) {
+                Ok(report) => {
+                    print_part_run(&part_run(&report), report.part);
+                    reports.push(report);
+                }
+                Err(_) => println!("{line}"),
+            }
+        }
+    }
+
+    child.wait()?;
+    Ok(reports)
+}
+
+/// Turns a report back into a run so it prints like the solution itself would.
+fn part_run(report: &PartReport) -> PartRun {
+    PartRun {
+        answer: report.answer.clone(),
+        error: report.error.clone(),
+        stats: BenchStats::single(Duration::from_nanos(report.nanos)),
+        memory: None,
+    }
+}
+
+fn answers(reports: &[PartReport]) -> Answers {
+    reports.iter().map(|x| (x.part, x.answer.clone())).collect()
+}
+
+fn diff(previous: &Answers, current: &Answers) -> Vec<String> {
+    let format = |answer: Option<&Option<String>>| match answer {
+        Some(Some(answer)) => answer.clone(),
+        _ => "✖".to_string(),
+    };
+
+    current
+        .keys()
+        .chain(previous.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|part| {
+            let before = format(previous.get(part));
+            let after = format(current.get(part));
+            if before == after {
+                format!("Part {part}: {after} {ANSI_ITALIC}(unchanged){ANSI_RESET}")
+            } else {
+                format!("Part {part}: {before} → {ANSI_BOLD}{after}{ANSI_RESET}")
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers, diff, Answers};
+    use crate::template::report::{PartReport, Status};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn reads_answers_from_reports() {
+        let report = |part: u8, answer: Option<&str>| PartReport {
+            year: 2023,
+            day: 1,
+            part,
+            variant: None,
+            status: if answer.is_some() {
+                Status::Solved
+            } else {
+                Status::None
+            },
+            answer: answer.map(Into::into),
+            error: None,
+            nanos: 1200,
+            samples: 1,
+        };
+
+        let answers = answers(&[report(1, Some("#.\n.#")), report(2, None)]);
+        assert_eq!(answers.get(&1), Some(&Some("#.\n.#".to_string())));
+        assert_eq!(answers.get(&2), Some(&None));
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let previous = Answers::from([(1, Some("42".into())), (2, None)]);
+        let current = Answers::from([(1, Some("42".into())), (2, Some("7".into()))]);
+
+        assert_eq!(
+            diff(&previous, &current),
+            [
+                format!("Part 1: 42 {ANSI_ITALIC}(unchanged){ANSI_RESET}"),
+                format!("Part 2: ✖ → {ANSI_BOLD}7{ANSI_RESET}"),
+            ]
+        );
+    }
+}
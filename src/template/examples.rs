@@ -0,0 +1,396 @@
+/// Module that extracts the examples of a puzzle from its stored markdown (see `aoc_client::html`).
+///
+/// An example is the last code block that is introduced as an example before the highlighted
+/// answer of a part, e.g. ``*`142`*``. Part two reuses the first example unless it introduces
+/// a new one, which is stored as a variant (`DD_2.txt`). Expected answers are written next to
+/// each example file as `DD.answers` (see [`Answers`]).
+///
+/// [`discover`] finds the example files of a day again, which lets [`check_part`] run a
+/// solution against all of them.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{
+    answers::{self, Answers},
+    data_dir,
+    runner::PartOutput,
+};
+use crate::{Day, Year};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Answers,
+}
+
+enum Event {
+    Block { is_example: bool, content: String },
+    Answer(String),
+}
+
+/// Finds the examples of both parts in the markdown of a puzzle.
+/// The first example is the main one, further entries are variants introduced by part two.
+#[must_use]
+pub fn extract(markdown: &str) -> Vec<Example> {
+    let mut events: [Vec<Event>; 2] = Default::default();
+    let mut part = 0;
+    // our html converter writes every paragraph on a single line.
+    let mut paragraph = "";
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if let Some(content) = block.as_mut() {
+            if line.starts_with("```") {
+                events[part].push(Event::Block {
+                    is_example: introduces_example(paragraph),
+                    content: block.take().unwrap_or_default(),
+                });
+                paragraph = "";
+            } else {
+                content.push_str(line);
+                content.push('\n');
+            }
+            continue;
+        }
+
+        if line.starts_with("```") {
+            block = Some(String::new());
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            if heading.contains("Part Two") {
+                part = 1;
+            }
+            paragraph = "";
+        } else if !line.trim().is_empty() {
+            paragraph = line;
+            events[part].extend(highlighted_values(line).map(Event::Answer));
+        }
+    }
+
+    let mut examples: Vec<Example> = vec![];
+
+    for (part, events) in (1..=2u8).zip(events.iter()) {
+        let answer_index = events
+            .iter()
+            .rposition(|event| matches!(event, Event::Answer(_)));
+
+        let answer = answer_index.and_then(|i| match &events[i] {
+            Event::Answer(answer) => Some(answer.clone()),
+            Event::Block { .. } => None,
+        });
+
+        let input = events[..answer_index.unwrap_or(events.len())]
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                Event::Block {
+                    is_example: true,
+                    content,
+                } => Some(content),
+                _ => None,
+            });
+
+        let example = match input {
+            Some(input) => match examples.iter().position(|x| &x.input == input) {
+                Some(i) => &mut examples[i],
+                None => {
+                    examples.push(Example {
+                        input: input.clone(),
+                        ..Example::default()
+                    });
+                    examples.last_mut().unwrap()
+                }
+            },
+            // part two without a new example refers back to the one of part one.
+            None => match examples.first_mut() {
+                Some(example) => example,
+                None => continue,
+            },
+        };
+
+        if let Some(answer) = answer {
+            example.answers.set(part, &answer);
+        }
+    }
+
+    examples
+}
+
+/// Whether a paragraph introduces a new example, rather than referring back to an earlier one.
+fn introduces_example(paragraph: &str) -> bool {
+    let paragraph = paragraph.to_ascii_lowercase();
+    paragraph.contains("example") && !paragraph.contains("above") && !paragraph.contains("same")
+}
+
+/// Values highlighted as ``*`value`*``.
+fn highlighted_values(line: &str) -> impl Iterator<Item = String> + '_ {
+    let mut rest = line;
+
+    std::iter::from_fn(move || {
+        let start = rest.find("*`")? + 2;
+        let len = rest[start..].find("`*")?;
+        let value = rest[start..start + len].to_string();
+        rest = &rest[start + len + 2..];
+        Some(value)
+    })
+}
+
+/// Path of an example file. The main example (`variant` 1) is `DD.txt`, variants are `DD_N.txt`.
+#[must_use]
+pub fn example_path(year: Year, day: Day, variant: usize) -> PathBuf {
+    let name = if variant <= 1 {
+        format!("{day}.txt")
+    } else {
+        format!("{day}_{variant}.txt")
+    };
+    data_dir(year).join("examples").join(name)
+}
+
+/// Path of the expected answers that belong to an example file.
+#[must_use]
+pub fn answers_path(example_path: &Path) -> PathBuf {
+    example_path.with_extension("answers")
+}
+
+/// An example file on disk, together with the expected answers stored next to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleFile {
+    /// `1` for `DD.txt`, `N` for `DD_N.txt`.
+    pub variant: usize,
+    pub path: PathBuf,
+    pub input: String,
+    pub answers: Answers,
+}
+
+/// The variant of an example file name, e.g. `08_2.txt` is variant 2 of day 8.
+fn parse_variant(file_name: &str, day: Day) -> Option<usize> {
+    let stem = file_name.strip_suffix(".txt")?;
+    if stem == day.to_string() {
+        return Some(1);
+    }
+    stem.strip_prefix(&format!("{day}_"))?
+        .parse()
+        .ok()
+        .filter(|x| *x > 1)
+}
+
+/// Finds every example of a day (`DD.txt`, `DD_2.txt`, ...), ordered by variant.
+pub fn discover(year: Year, day: Day) -> Result<Vec<ExampleFile>, answers::Error> {
+    let folder = data_dir(year).join("examples");
+
+    let entries = match fs::read_dir(&folder) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut examples = vec![];
+
+    for entry in entries {
+        let path = entry?.path();
+        let Some(variant) = path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .and_then(|x| parse_variant(x, day))
+        else {
+            continue;
+        };
+
+        examples.push(ExampleFile {
+            variant,
+            input: fs::read_to_string(&path)?,
+            answers: Answers::load(&answers_path(&path))?,
+            path,
+        });
+    }
+
+    examples.sort_by_key(|x| x.variant);
+    Ok(examples)
+}
+
+/// Runs a part of the default year against every example that has an expected answer for it.
+/// Used by the tests of scaffolded solutions, see [`check_part_for_year`].
+#[track_caller]
+pub fn check_part<O: PartOutput>(day: Day, part: u8, func: impl Fn(&str) -> O) {
+    check_part_for_year(Year::from_env(), day, part, func);
+}
+
+/// Runs a part against every example that has an expected answer for it. The part can return
+/// anything the runner accepts (see [`PartOutput`]).
+/// Parts without any expected answer pass with a note, so freshly scaffolded days do not start
+/// with failing tests.
+///
+/// # Panics
+/// On the first example whose result differs from the expected answer, or for which the part
+/// returns an error.
+#[track_caller]
+pub fn check_part_for_year<O: PartOutput>(
+    year: Year,
+    day: Day,
+    part: u8,
+    func: impl Fn(&str) -> O,
+) {
+    let examples = discover(year, day)
+        .unwrap_or_else(|e| panic!("could not load the examples of day {day}: {e}"));
+
+    let mut checked = 0;
+
+    for example in &examples {
+        let Some(expected) = example.answers.get(part) else {
+            continue;
+        };
+
+        let result = func(&example.input).answer().unwrap_or_else(|e| {
+            panic!(
+                "part {part} of example \"{}\" failed: {e}",
+                example.path.display()
+            )
+        });
+        assert_eq!(
+            result.as_deref(),
+            Some(expected),
+            "part {part} of example \"{}\"",
+            example.path.display()
+        );
+        checked += 1;
+    }
+
+    if checked == 0 {
+        eprintln!(
+            "Skipped: no example of day {day} has an expected answer for part {part}. Run `cargo extract {day}` or add it to \"{}\".",
+            answers_path(&example_path(year, day, 1)).display()
+        );
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_part_for_year, discover, extract, parse_variant, Example};
+    use crate::template::answers::Answers;
+    use crate::{day, solutions, year, Year};
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        let markdown = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "treb7uchet",
+            "```",
+            "",
+            "In this example, the values of these lines are `12` and `77`. Adding these together produces *`89`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "Equipped with this new information, consider the following example:",
+            "",
+            "```",
+            "two1nine",
+            "```",
+            "",
+            "Here is the example above, with the digits marked:",
+            "",
+            "```",
+            "*2*1*9*",
+            "```",
+            "",
+            "The result is *`29`*.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract(&markdown),
+            vec![
+                Example {
+                    input: "1abc2\ntreb7uchet\n".into(),
+                    answers: Answers {
+                        part_one: Some("89".into()),
+                        part_two: None,
+                    },
+                },
+                Example {
+                    input: "two1nine\n".into(),
+                    answers: Answers {
+                        part_one: None,
+                        part_two: Some("29".into()),
+                    },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn part_two_reuses_the_first_example() {
+        let markdown = [
+            "## --- Day 11: Cosmic Expansion ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "...#",
+            "```",
+            "",
+            "The sum is *`374`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "In the example above, the sum would be *`1030`* or *`8410`*.",
+        ]
+        .join("\n");
+
+        let examples = extract(&markdown);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].answers.get(1), Some("374"));
+        assert_eq!(examples[0].answers.get(2), Some("8410"));
+    }
+
+    #[test]
+    fn discovers_example_variants() {
+        let examples = discover(Year::from_env(), day!(8)).unwrap();
+        let variants: Vec<_> = examples.iter().map(|x| x.variant).collect();
+        assert_eq!(variants, vec![1, 2]);
+        assert_eq!(examples[1].answers.get(2), Some("6"));
+    }
+
+    #[test]
+    fn parses_variant_file_names() {
+        assert_eq!(parse_variant("08.txt", day!(8)), Some(1));
+        assert_eq!(parse_variant("08_2.txt", day!(8)), Some(2));
+        assert_eq!(parse_variant("08_2.answers", day!(8)), None);
+        assert_eq!(parse_variant("08_1.txt", day!(8)), None);
+        assert_eq!(parse_variant("18.txt", day!(8)), None);
+        assert_eq!(parse_variant("08-2.txt", day!(8)), None);
+    }
+
+    #[test]
+    fn checks_parts_that_return_results() {
+        // the examples of day 8 have answers for both parts.
+        check_part_for_year(year!(2023), day!(8), 1, |input| {
+            solutions::get(year!(2023), day!(8))
+                .unwrap()
+                .part_one(input)
+                .map(Option::unwrap)
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "failed: invalid input")]
+    fn fails_on_errors() {
+        check_part_for_year(year!(2023), day!(8), 1, |_| -> Result<u64, _> {
+            Err("invalid input")
+        });
+    }
+
+    #[test]
+    fn skips_parts_without_answers() {
+        // no event of 2015 is stored, so there are no examples to check the part against.
+        check_part_for_year(year!(2015), day!(1), 1, |_| -> Option<u8> {
+            panic!("the part should not run")
+        });
+    }
+}
@@ -0,0 +1,433 @@
+/// Module that selects the input a solution runs against and checks it before it is solved.
+/// `cargo solve` forwards `--input <path>`, `--input -` (stdin) or `--example <N>` to the
+/// solution binary, which reads them back via [`InputSource::from_args`].
+/// Inputs are [normalised](normalize) and checked against the [`Shape`] and line pattern a day
+/// declares, so empty, truncated or mixed up inputs fail with a message instead of a panic deep
+/// in a solution. Inputs of days with similar lines, e.g. grids of the same characters, can not
+/// be told apart.
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+};
+
+use regex::Regex;
+
+use crate::template::{examples::example_path, try_read_file};
+use crate::{Day, Year};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The downloaded puzzle input, `data/inputs/DD.txt`.
+    #[default]
+    Puzzle,
+    File(PathBuf),
+    Stdin,
+    /// An example variant, `1` for `DD.txt` and `N` for `DD_N.txt`.
+    Example(usize),
+}
+
+impl InputSource {
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|index| {
+                    args.get(index + 1)
+                        .ok_or(format!("expected a value after {flag}"))
+                })
+                .transpose()
+        };
+
+        let example = value_of("--example")?
+            .map(|variant| {
+                variant.parse().map_err(|_| {
+                    format!("expected an example number after --example, got {variant:?}")
+                })
+            })
+            .transpose()?;
+
+        Self::from_options(value_of("--input")?.cloned(), example)
+    }
+
+    /// Builds the source from the values of `--input` and `--example`.
+    pub fn from_options(input: Option<String>, example: Option<usize>) -> Result<Self, String> {
+        match (input, example) {
+            (Some(_), Some(_)) => Err("--input and --example can not be combined".into()),
+            (Some(path), None) if path == "-" => Ok(Self::Stdin),
+            (Some(path), None) => Ok(Self::File(PathBuf::from(path))),
+            (None, Some(0)) => Err("examples are numbered from 1".into()),
+            (None, Some(variant)) => Ok(Self::Example(variant)),
+            (None, None) => Ok(Self::Puzzle),
+        }
+    }
+
+    /// Turns the source back into flags so it can be forwarded to the solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--input".into(), "-".into()],
+            Self::Example(variant) => vec!["--example".into(), variant.to_string()],
+        }
+    }
+
+    pub fn read(&self, year: Year, day: Day) -> io::Result<String> {
+        match self {
+            Self::Puzzle => try_read_file(year, "inputs", day),
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Self::Example(variant) => fs::read_to_string(example_path(year, day, *variant)),
+        }
+    }
+
+    /// Where the input is read from, for error messages.
+    #[must_use]
+    pub fn describe(&self, year: Year, day: Day) -> String {
+        match self {
+            Self::Puzzle => format!("the puzzle input of day {day}"),
+            Self::File(path) => format!("\"{}\"", path.display()),
+            Self::Stdin => "stdin".into(),
+            Self::Example(variant) => {
+                format!("\"{}\"", example_path(year, day, *variant).display())
+            }
+        }
+    }
+}
+
+/// The layout of the puzzle input of a day, declared via [`solution!`](crate::solution).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// A rectangle of characters, e.g. a map.
+    Grid,
+    /// At least two blocks of lines separated by single blank lines.
+    Blocks,
+    /// One record per line, without blank lines.
+    Lines,
+}
+
+impl Shape {
+    /// Checks that a [normalised](normalize) input has this shape, returning what is wrong otherwise.
+    pub fn check(self, input: &str) -> Result<(), String> {
+        let blank_line = input.lines().position(|x| x.trim().is_empty());
+
+        match self {
+            Self::Grid => {
+                if let Some(index) = blank_line {
+                    return Err(format!("line {} is blank", index + 1));
+                }
+                let width = input.lines().next().map_or(0, str::len);
+                match input.lines().position(|x| x.len() != width) {
+                    Some(index) => Err(format!(
+                        "line {} has {} characters, the lines before it have {width}",
+                        index + 1,
+                        input.lines().nth(index).map_or(0, str::len)
+                    )),
+                    None => Ok(()),
+                }
+            }
+            Self::Blocks => {
+                let lines: Vec<&str> = input.lines().collect();
+                let is_blank = |index: usize| lines[index].trim().is_empty();
+
+                match blank_line {
+                    None => Err("there is no blank line between blocks".into()),
+                    Some(0) => Err("line 1 is blank".into()),
+                    Some(first) => {
+                        match (first + 1..lines.len()).find(|&x| is_blank(x) && is_blank(x - 1)) {
+                            Some(index) => {
+                                Err(format!("line {} is a second blank line", index + 1))
+                            }
+                            None => Ok(()),
+                        }
+                    }
+                }
+            }
+            Self::Lines => match blank_line {
+                Some(index) => Err(format!("line {} is blank", index + 1)),
+                None => Ok(()),
+            },
+        }
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Grid => "a grid",
+            Self::Blocks => "blocks separated by blank lines",
+            Self::Lines => "one record per line",
+        })
+    }
+}
+
+/// Normalises line endings to `\n` and makes a non-empty input end with exactly one newline.
+#[must_use]
+pub fn normalize(input: &str) -> String {
+    let input = input.replace("\r\n", "\n");
+    let input = input.trim_end_matches(['\n', '\r']);
+
+    if input.is_empty() {
+        String::new()
+    } else {
+        format!("{input}\n")
+    }
+}
+
+/// Checks a [normalised](normalize) input: it must not be empty, it must have the shape the
+/// day declared, and every line that is not blank must match the pattern the day declared.
+///
+/// # Panics
+/// If the pattern is not a valid regex.
+pub fn validate(
+    input: &str,
+    shape: Option<Shape>,
+    pattern: Option<&str>,
+) -> Result<(), InputError> {
+    if input.trim().is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    if let Some(shape) = shape {
+        shape
+            .check(input)
+            .map_err(|message| InputError::Shape { shape, message })?;
+    }
+
+    if let Some(pattern) = pattern {
+        let regex = compiled(pattern);
+
+        if let Some(index) = input
+            .lines()
+            .position(|x| !x.trim().is_empty() && !regex.is_match(x))
+        {
+            return Err(InputError::Pattern {
+                line: index + 1,
+                pattern: pattern.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Compiles a line pattern once, `all` validates the input of every day and `verify` every example.
+///
+/// # Panics
+/// If the pattern is not a valid regex.
+fn compiled(pattern: &str) -> Regex {
+    static PATTERNS: OnceLock<Mutex<HashMap<String, Regex>>> = OnceLock::new();
+
+    let mut patterns = PATTERNS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+
+    patterns
+        .entry(pattern.to_string())
+        .or_insert_with(|| {
+            Regex::new(pattern).unwrap_or_else(|e| panic!("invalid input pattern {pattern:?}: {e}"))
+        })
+        .clone()
+}
+
+/// Reads the puzzle input of a day, normalises it and checks it against the shape and the line
+/// pattern of the day.
+pub fn load(
+    year: Year,
+    day: Day,
+    shape: Option<Shape>,
+    pattern: Option<&str>,
+) -> Result<String, InputError> {
+    let input = normalize(&try_read_file(year, "inputs", day)?);
+    validate(&input, shape, pattern)?;
+    Ok(input)
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(io::Error),
+    /// The input is empty, e.g. because `scaffold` created the file but it was never downloaded.
+    Empty,
+    /// The input does not have the shape the day declared.
+    Shape {
+        shape: Shape,
+        message: String,
+    },
+    /// A line does not match the pattern the day declared.
+    Pattern {
+        line: usize,
+        pattern: String,
+    },
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::Empty => write!(f, "the input is empty, was it downloaded?"),
+            Self::Shape { shape, message } => write!(
+                f,
+                "expected {shape}, but {message}. Is the input cut off or from another day?"
+            ),
+            Self::Pattern { line, pattern } => write!(
+                f,
+                "expected every line to match `{pattern}`, but line {line} does not. Is the input from another day?"
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for InputError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Reads the input selected on the command line, normalises it and checks it against the
+/// `shape` and line `pattern` of the day. The error names the input that could not be used.
+pub fn read_input(
+    year: Year,
+    day: Day,
+    shape: Option<Shape>,
+    pattern: Option<&str>,
+) -> Result<String, String> {
+    let args: Vec<String> = env::args().collect();
+
+    let source = InputSource::from_args(&args).map_err(|e| format!("Invalid input option: {e}"))?;
+
+    let input = normalize(
+        &source
+            .read(year, day)
+            .map_err(|e| format!("Could not read {}: {e}", source.describe(year, day)))?,
+    );
+
+    validate(&input, shape, pattern)
+        .map_err(|e| format!("Could not use {}: {e}", source.describe(year, day)))?;
+
+    Ok(input)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{normalize, validate, InputError, InputSource, Shape};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn round_trips_through_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::File(PathBuf::from("edge/case.txt")),
+            InputSource::Stdin,
+            InputSource::Example(2),
+        ] {
+            assert_eq!(InputSource::from_args(&source.to_args()), Ok(source));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_args() {
+        assert!(InputSource::from_args(&args(&["--input"])).is_err());
+        assert!(InputSource::from_args(&args(&["--example", "0"])).is_err());
+        assert!(InputSource::from_args(&args(&["--input", "-", "--example", "1"])).is_err());
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb\n");
+        assert_eq!(normalize("a\n\nb"), "a\n\nb\n");
+        assert_eq!(normalize("\n\r\n"), "");
+    }
+
+    #[test]
+    fn checks_shapes() {
+        assert!(matches!(validate("", None, None), Err(InputError::Empty)));
+        assert!(validate(" \n", Some(Shape::Lines), None).is_err());
+
+        assert_eq!(Shape::Grid.check("#.\n.#\n"), Ok(()));
+        assert_eq!(
+            Shape::Grid.check("#.#\n.#.\n#.\n"),
+            Err("line 3 has 2 characters, the lines before it have 3".into())
+        );
+        assert!(Shape::Grid.check("#.\n\n.#\n").is_err());
+
+        assert_eq!(Shape::Blocks.check("a\nb\n\nc\n"), Ok(()));
+        assert!(Shape::Blocks.check("a\nb\n").is_err());
+        assert!(Shape::Blocks.check("a\n\n\nb\n").is_err());
+        assert!(Shape::Blocks.check("\na\n").is_err());
+
+        assert_eq!(Shape::Lines.check("1 2\n3 4\n"), Ok(()));
+        assert!(Shape::Lines.check("1\n\n2\n").is_err());
+    }
+
+    #[test]
+    fn checks_line_patterns() {
+        let pattern = Some(r"^\d+ \d+$");
+        assert!(validate("1 2\n3 4\n", Some(Shape::Lines), pattern).is_ok());
+        assert!(matches!(
+            validate("1 2\n3; 4\n", Some(Shape::Lines), pattern),
+            Err(InputError::Pattern { line: 2, .. })
+        ));
+        // blank lines are left to the shape.
+        assert!(validate("1 2\n\n3 4\n", Some(Shape::Blocks), pattern).is_ok());
+    }
+
+    #[test]
+    fn tells_examples_of_other_days_apart() {
+        use crate::template::{examples, registry};
+
+        // grids of a few characters, e.g. `.` and `#`, can't be told apart from each other.
+        let is_character_grid = |pattern: Option<&str>| {
+            pattern.is_some_and(|x| {
+                x.strip_prefix("^[")
+                    .and_then(|x| x.strip_suffix("]+$"))
+                    .is_some_and(|x| !x.contains(']'))
+            })
+        };
+
+        let days: Vec<_> = registry::solutions()
+            .iter()
+            .filter(|x| x.variant.is_none())
+            .collect();
+
+        for solution in &days {
+            for example in examples::discover(solution.year(), solution.day).unwrap() {
+                let input = normalize(&example.input);
+
+                assert!(
+                    validate(&input, solution.shape, solution.pattern).is_ok(),
+                    "{} does not match its own day",
+                    example.path.display()
+                );
+
+                for other in days.iter().filter(|x| x.day != solution.day) {
+                    assert!(
+                        validate(&input, other.shape, other.pattern).is_err()
+                            || is_character_grid(solution.pattern)
+                                && is_character_grid(other.pattern),
+                        "{} matches day {}",
+                        example.path.display(),
+                        other.day
+                    );
+                }
+            }
+        }
+    }
+}
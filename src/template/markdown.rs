@@ -0,0 +1,257 @@
+/// Module that renders stored puzzle descriptions (`data/puzzles/DD.md`) for the terminal.
+/// Only the markdown written by the puzzle download is supported: `##` headings, paragraphs,
+/// list items, fenced code blocks, `*emphasis*`, `` `code` `` and links.
+use std::{
+    env,
+    io::{self, BufRead, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use regex::Regex;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_WIDTH: usize = 80;
+const DEFAULT_HEIGHT: usize = 24;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+}
+
+impl Style {
+    fn codes(self) -> String {
+        let mut codes = String::new();
+        if self.bold {
+            codes.push_str(ANSI_BOLD);
+        }
+        if self.italic {
+            codes.push_str(ANSI_ITALIC);
+        }
+        codes
+    }
+}
+
+/// A word of styled characters, words are separated by whitespace when wrapping.
+type Word = Vec<(char, Style)>;
+
+/// Splits a line of inline markdown into styled words. Emphasis is bold and code is italic,
+/// so highlighted values like ``*`142`*`` are both. Links are reduced to their text.
+fn parse_inline(text: &str) -> Vec<Word> {
+    let links = Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap();
+    let text = links.replace_all(text, "$1");
+
+    let mut words: Vec<Word> = vec![];
+    let mut word: Word = vec![];
+    let mut style = Style::default();
+
+    for c in text.chars() {
+        match c {
+            '`' => style.italic = !style.italic,
+            '*' if !style.italic => style.bold = !style.bold,
+            c if c.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push((c, style)),
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn render_word(word: &Word) -> String {
+    let mut out = String::new();
+    let mut current = Style::default();
+
+    for &(c, style) in word {
+        if style != current {
+            if current != Style::default() {
+                out.push_str(ANSI_RESET);
+            }
+            out.push_str(&style.codes());
+            current = style;
+        }
+        out.push(c);
+    }
+
+    if current != Style::default() {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// Wraps styled words to `width` visible characters. Continuation lines are indented by `indent`.
+fn wrap(words: &[Word], width: usize, first_prefix: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_prefix.to_string();
+    let mut line_width = first_prefix.chars().count();
+    let mut is_empty = true;
+
+    for word in words {
+        let word_width = word.len();
+
+        if !is_empty && line_width + 1 + word_width > width {
+            lines.push(std::mem::replace(&mut line, indent.to_string()));
+            line_width = indent.chars().count();
+            is_empty = true;
+        }
+
+        if !is_empty {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(&render_word(word));
+        line_width += word_width;
+        is_empty = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+/// Renders puzzle markdown to lines of terminal output, wrapped to `width` columns.
+#[must_use]
+pub fn render(markdown: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            // code blocks are grids and tables more often than not, they are never wrapped.
+            lines.push(format!("    {line}"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            lines.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            lines.extend(wrap(&parse_inline(item), width, "  • ", "    "));
+        } else if line.trim().is_empty() {
+            lines.push(String::new());
+        } else {
+            lines.extend(wrap(&parse_inline(line), width, "", ""));
+        }
+    }
+
+    lines
+}
+
+/// The width of the terminal from `COLUMNS`, falling back to 80 columns.
+#[must_use]
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .filter(|x| *x > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+fn terminal_height() -> usize {
+    env::var("LINES")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .filter(|x| *x > 1)
+        .unwrap_or(DEFAULT_HEIGHT)
+}
+
+/// Prints lines, paging them if they do not fit on the terminal.
+/// Uses `$PAGER` if it is set, otherwise a built-in pager that advances one screen per Enter.
+pub fn page(lines: &[String]) -> io::Result<()> {
+    let height = terminal_height();
+
+    if !io::stdout().is_terminal() || lines.len() < height {
+        for line in lines {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
+    if let Some(pager) = env::var("PAGER").ok().filter(|x| !x.trim().is_empty()) {
+        let mut parts = pager.split_whitespace();
+        let program = parts.next().unwrap_or_default();
+
+        if let Ok(mut child) = Command::new(program)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .spawn()
+        {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the pager closes its input when it is quit early, which is not an error.
+                let _ = stdin.write_all(lines.join("\n").as_bytes());
+            }
+            child.wait()?;
+            return Ok(());
+        }
+    }
+
+    let mut stdout = io::stdout().lock();
+    let mut input = io::stdin().lock();
+
+    for (index, screen) in lines.chunks(height - 1).enumerate() {
+        if index > 0 {
+            write!(
+                stdout,
+                "{ANSI_ITALIC}-- more, Enter to continue, q to quit --{ANSI_RESET}"
+            )?;
+            stdout.flush()?;
+
+            let mut answer = String::new();
+            input.read_line(&mut answer)?;
+            if answer.trim().eq_ignore_ascii_case("q") {
+                break;
+            }
+        }
+
+        for line in screen {
+            writeln!(stdout, "{line}")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn renders_puzzle_markdown() {
+        let markdown = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "```",
+            "1abc2 with a long line that is not wrapped",
+            "```",
+            "",
+            "Adding these produces *`142`*, see [your input](/2023/day/1/input) & *more*.",
+            "- `two`",
+        ]
+        .join("\n");
+
+        let lines = render(&markdown, 30);
+        assert_eq!(
+            lines,
+            [
+                format!("{ANSI_BOLD}--- Day 1: Trebuchet?! ---{ANSI_RESET}"),
+                String::new(),
+                "    1abc2 with a long line that is not wrapped".to_string(),
+                String::new(),
+                format!("Adding these produces {ANSI_BOLD}{ANSI_ITALIC}142{ANSI_RESET}, see"),
+                format!("your input & {ANSI_BOLD}more{ANSI_RESET}."),
+                format!("  • {ANSI_ITALIC}two{ANSI_RESET}"),
+            ]
+        );
+    }
+}
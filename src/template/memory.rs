@@ -0,0 +1,197 @@
+/// Module that measures the heap usage of solution parts.
+/// With the `memory` feature, a counting global allocator tracks the live heap size and the number of
+/// allocations, so [`measure`] can report the peak heap usage of a single run. The counters are kept
+/// per thread, so parts that run concurrently (`all --jobs`) or keep running in the background after
+/// a timeout do not show up in the figures of other parts. Threads spawned by a part are not counted.
+use std::fmt::Display;
+
+/// Heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// The highest heap size reached during the run, above the heap size at its start.
+    pub peak_bytes: usize,
+    /// Number of allocations and reallocations during the run.
+    pub allocations: usize,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} allocations",
+            format_bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 MiB`.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Whether heap usage is measured, i.e. whether the crate was built with the `memory` feature.
+#[must_use]
+pub const fn is_enabled() -> bool {
+    cfg!(feature = "memory")
+}
+
+/// Runs `func` and measures its heap usage. Returns [`None`] for the stats without the `memory` feature.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    #[cfg(feature = "memory")]
+    {
+        let baseline = counting::reset();
+        let result = func();
+        (result, Some(counting::stats(baseline)))
+    }
+
+    #[cfg(not(feature = "memory"))]
+    {
+        (func(), None)
+    }
+}
+
+#[cfg(feature = "memory")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use super::MemoryStats;
+
+    // the counters are per thread, `const` and without destructors so the allocator can use them
+    // without allocating itself. The heap size is signed because a thread can free memory that was
+    // allocated by another one.
+    thread_local! {
+        static CURRENT: Cell<isize> = const { Cell::new(0) };
+        static PEAK: Cell<isize> = const { Cell::new(0) };
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn grow(size: usize) {
+        let _ = CURRENT.try_with(|current| {
+            current.set(current.get() + size as isize);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+        });
+        let _ = ALLOCATIONS.try_with(|x| x.set(x.get() + 1));
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn shrink(size: usize) {
+        let _ = CURRENT.try_with(|current| current.set(current.get() - size as isize));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                grow(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            shrink(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                shrink(layout.size());
+                grow(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Resets the peak and the allocation count of the current thread, returns its heap size.
+    pub(super) fn reset() -> isize {
+        let current = CURRENT.with(Cell::get);
+        PEAK.with(|x| x.set(current));
+        ALLOCATIONS.with(|x| x.set(0));
+        current
+    }
+
+    pub(super) fn stats(baseline: isize) -> MemoryStats {
+        MemoryStats {
+            peak_bytes: (PEAK.with(Cell::get) - baseline)
+                .try_into()
+                .unwrap_or_default(),
+            allocations: ALLOCATIONS.with(Cell::get),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure, MemoryStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+
+        let stats = MemoryStats {
+            peak_bytes: 2048,
+            allocations: 3,
+        };
+        assert_eq!(stats.to_string(), "2.0 KiB peak, 3 allocations");
+    }
+
+    #[test]
+    fn ignores_allocations_of_other_threads() {
+        let (_, stats) = measure(|| {
+            std::thread::spawn(|| vec![0_u8; 1 << 20].len())
+                .join()
+                .unwrap()
+        });
+
+        if let Some(stats) = stats {
+            assert!(stats.peak_bytes < 1 << 20);
+        }
+    }
+
+    #[test]
+    fn measures_allocations() {
+        let (len, stats) = measure(|| vec![0_u8; 1 << 20].len());
+        assert_eq!(len, 1 << 20);
+
+        if cfg!(feature = "memory") {
+            let stats = stats.unwrap();
+            assert!(stats.peak_bytes > 0);
+            assert!(stats.allocations >= 1);
+        } else {
+            assert!(stats.is_none());
+        }
+    }
+}
@@ -1,48 +1,183 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Year};
+use std::{env, fs, io, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
+pub mod benchmark_compare;
+pub mod benchmark_export;
 pub mod commands;
+pub mod examples;
+pub mod input;
+pub mod markdown;
+pub mod memory;
+pub mod progress;
 pub mod readme_benchmarks;
+pub mod readme_progress;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod submissions;
+#[cfg(any(test, feature = "test_lib"))]
+pub mod testing;
+pub mod variants;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Folder that holds the `inputs`, `examples` and `puzzles` of a year.
+/// The default year (see [`Year::from_env`]) uses `data/`, every other year `data/<year>/`.
+#[must_use]
+pub fn data_dir(year: Year) -> PathBuf {
+    if year.is_default() {
+        PathBuf::from("data")
+    } else {
+        PathBuf::from("data").join(year.to_string())
+    }
+}
+
+/// Name of the binary that solves a day, `DD` for the default year and `YYYY-DD` otherwise.
+#[must_use]
+pub fn bin_name(year: Year, day: Day) -> String {
+    if year.is_default() {
+        day.to_string()
+    } else {
+        format!("{year}-{day}")
+    }
+}
+
+/// Path of the module that solves a day, relative to the crate root.
+#[must_use]
+pub fn bin_path(year: Year, day: Day) -> String {
+    format!("./src/bin/{}.rs", bin_name(year, day))
+}
+
+/// Helper function that reads a text file of the default year to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_file_for_year(Year::from_env(), folder, day)
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of a year to a string, see [`input::normalize`].
+#[must_use]
+pub fn read_file_for_year(year: Year, folder: &str, day: Day) -> String {
+    let f = try_read_file(year, folder, day);
+    input::normalize(&f.expect("could not open input file"))
+}
+
+/// Helper function that reads a text file to a string, returning an error instead of panicking.
+/// The file is returned as is, see [`input::load`] for the checked puzzle input.
+pub fn try_read_file(year: Year, folder: &str, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd
+        .join(data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
+    fs::read_to_string(filepath)
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `08_2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(Year::from_env()))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{day}_{part}.txt"));
     let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    input::normalize(&f.expect("could not open input file"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// Solutions of the default year are declared as `solution!(5)`, other years also
+/// name the event, e.g. `solution!(2024, 5)`, which additionally creates the constant `YEAR`.
+/// Further variants of a day use the same declaration in a file named after the variant, e.g.
+/// `src/bin/10-softwaregore.rs`, see [`registry::variants`].
+/// The layout of the input can be declared as well, e.g. `solution!(14, shape = Grid)`, optionally
+/// followed by a regex every line matches, e.g. `solution!(14, shape = Grid, pattern = "^[.#O]+$")`.
+/// Inputs that do not match fail before they are solved (see [`input::validate`]).
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@impl $year:expr, $registry_year:expr, $day:expr, $shape:expr, $pattern:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// Registry entry that lets the `all` command call this day in-process.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                year: $registry_year,
+                day: DAY,
+                // variants are named after their file by `build.rs`.
+                variant: None,
+                shape: $shape,
+                pattern: $pattern,
+                part_one: |input, config| {
+                    advent_of_code::template::runner::measure_part(part_one, input, 1, config)
+                },
+                part_two: |input, config| {
+                    advent_of_code::template::runner::measure_part(part_two, input, 2, config)
+                },
+                answer_one: |input| advent_of_code::solutions::IntoAnswer::into_answer(part_one(input)),
+                answer_two: |input| advent_of_code::solutions::IntoAnswer::into_answer(part_two(input)),
+            };
+
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let year: advent_of_code::Year = $year;
+            let input = advent_of_code::template::input::read_input(
+                year,
+                DAY,
+                SOLUTION.shape,
+                SOLUTION.pattern,
+            )
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                std::process::exit(1);
+            });
+            run_part(part_one, &input, year, DAY, 1);
+            // the last day of an event usually has a single part.
+            if advent_of_code::Event::for_year(year).parts(DAY) > 1 {
+                run_part(part_two, &input, year, DAY, 2);
+            }
         }
     };
+    (@pattern) => {
+        None
+    };
+    (@pattern $pattern:literal) => {
+        Some($pattern)
+    };
+    // the shape arms come first, `shape = Grid` would otherwise be parsed as the day.
+    ($day:expr, shape = $shape:ident $(, pattern = $pattern:literal)?) => {
+        advent_of_code::solution!(
+            @impl advent_of_code::Year::from_env(),
+            None,
+            $day,
+            Some(advent_of_code::template::input::Shape::$shape),
+            advent_of_code::solution!(@pattern $($pattern)?)
+        );
+    };
+    ($year:expr, $day:expr, shape = $shape:ident $(, pattern = $pattern:literal)?) => {
+        /// The event year.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        advent_of_code::solution!(
+            @impl YEAR,
+            Some(YEAR),
+            $day,
+            Some(advent_of_code::template::input::Shape::$shape),
+            advent_of_code::solution!(@pattern $($pattern)?)
+        );
+    };
+    ($day:expr) => {
+        advent_of_code::solution!(@impl advent_of_code::Year::from_env(), None, $day, None, None);
+    };
+    ($year:expr, $day:expr) => {
+        /// The event year.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
+
+        advent_of_code::solution!(@impl YEAR, Some(YEAR), $day, None, None);
+    };
 }
@@ -0,0 +1,248 @@
+/// Module that works out which days and parts of an event are solved.
+/// A part counts as solved if the submission ledger has an accepted answer for it, or if the answer
+/// manifest (`data/answers/DD.answers`) knows its answer. Other parts of scaffolded days are run
+/// against the puzzle input to find out whether they produce an answer yet.
+use std::{path::Path, sync::Arc, time::Duration};
+
+use crate::template::submissions::Ledger;
+use crate::template::{
+    answers::{self, Answers},
+    bin_path, input, registry,
+    runner::{isolate, PartOutcome, RunConfig},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{Day, Event, Year};
+
+/// Time a part may take while checking whether it produces an answer.
+const PART_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PartProgress {
+    NotStarted,
+    /// The day is scaffolded, but the part does not produce an answer yet.
+    Started,
+    /// The part produces an answer that was not accepted yet.
+    Answered,
+    Solved,
+}
+
+impl PartProgress {
+    fn symbol(self) -> &'static str {
+        match self {
+            PartProgress::NotStarted => " ",
+            PartProgress::Started => "·",
+            PartProgress::Answered => "☆",
+            PartProgress::Solved => "★",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub parts: [PartProgress; 2],
+}
+
+impl DayProgress {
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.parts
+            .iter()
+            .filter(|x| **x == PartProgress::Solved)
+            .count()
+    }
+}
+
+pub struct Calendar {
+    pub year: Year,
+    pub days: Vec<DayProgress>,
+}
+
+impl Calendar {
+    /// Collects the progress of every day of an event.
+    #[must_use]
+    pub fn collect(year: Year, ledger: &Ledger) -> Self {
+        let event = Event::for_year(year);
+        let days = event
+            .days()
+            .map(|day| DayProgress {
+                day,
+                parts: collect_day(year, day, event.parts(day), ledger),
+            })
+            .collect();
+
+        Self::new(year, days)
+    }
+
+    /// Creates a calendar. Days with a single part, like the last day of Advent of Code, award
+    /// their second star once every other star is collected.
+    #[must_use]
+    pub fn new(year: Year, mut days: Vec<DayProgress>) -> Self {
+        let event = Event::for_year(year);
+        let is_single_part = |x: &DayProgress| event.parts(x.day) == 1;
+
+        let single_part_days = days.iter().filter(|x| is_single_part(x)).count();
+        let other_stars = days
+            .iter()
+            .map(|x| {
+                if is_single_part(x) {
+                    usize::from(x.parts[0] == PartProgress::Solved)
+                } else {
+                    x.stars()
+                }
+            })
+            .sum::<usize>();
+
+        if other_stars == days.len() * 2 - single_part_days {
+            for day in days.iter_mut().filter(|x| is_single_part(x)) {
+                day.parts[1] = PartProgress::Solved;
+            }
+        }
+
+        Self { year, days }
+    }
+
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(DayProgress::stars).sum()
+    }
+
+    #[must_use]
+    pub fn max_stars(&self) -> usize {
+        self.days.len() * 2
+    }
+
+    /// Renders the calendar for the terminal, five days per row.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "{ANSI_BOLD}Advent of Code {}: {}/{} stars{ANSI_RESET}",
+                self.year,
+                self.stars(),
+                self.max_stars()
+            ),
+            String::new(),
+        ];
+
+        for week in self.days.chunks(5) {
+            let cells: Vec<String> = week
+                .iter()
+                .map(|x| {
+                    let parts: String = x
+                        .parts
+                        .iter()
+                        .map(|part| match part {
+                            PartProgress::Solved => {
+                                format!("{ANSI_BOLD}{}{ANSI_RESET}", part.symbol())
+                            }
+                            _ => part.symbol().to_string(),
+                        })
+                        .collect();
+                    format!("{:>4} {parts}", x.day.into_inner())
+                })
+                .collect();
+            lines.push(cells.join("  "));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{ANSI_ITALIC}★ solved  ☆ answered, not accepted yet  · scaffolded{ANSI_RESET}"
+        ));
+
+        lines.join("\n")
+    }
+}
+
+fn collect_day(year: Year, day: Day, part_count: u8, ledger: &Ledger) -> [PartProgress; 2] {
+    let manifest = Answers::load(&answers::manifest_path(year, day)).unwrap_or_default();
+    let is_solved = |part: u8| {
+        ledger.solution(year.into_inner(), day, part).is_some() || manifest.get(part).is_some()
+    };
+
+    let mut parts = [1, 2].map(|part| {
+        if is_solved(part) {
+            PartProgress::Solved
+        } else {
+            PartProgress::NotStarted
+        }
+    });
+
+    let is_scaffolded = Path::new(&bin_path(year, day)).exists();
+    if !is_scaffolded || parts.iter().all(|x| *x == PartProgress::Solved) {
+        return parts;
+    }
+
+    let solution = registry::get(year, day);
+    let shape = solution.and_then(|x| x.shape);
+    let pattern = solution.and_then(|x| x.pattern);
+    let input: Option<Arc<str>> = input::load(year, day, shape, pattern).ok().map(Into::into);
+
+    let config = RunConfig {
+        bench: None,
+        quiet: true,
+    };
+
+    for (part, progress) in (1..=part_count).zip(parts.iter_mut()) {
+        if *progress == PartProgress::Solved {
+            continue;
+        }
+
+        let outcome = match (solution, &input) {
+            (Some(solution), Some(input)) => {
+                let func = if part == 1 {
+                    solution.part_one
+                } else {
+                    solution.part_two
+                };
+                Some(isolate(func, Arc::clone(input), config, Some(PART_TIMEOUT)))
+            }
+            _ => None,
+        };
+
+        *progress = match outcome {
+            Some(PartOutcome::Finished(run)) if run.answer.is_some() => PartProgress::Answered,
+            _ => PartProgress::Started,
+        };
+    }
+
+    parts
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Calendar, DayProgress, PartProgress};
+    use crate::{year, Event};
+
+    fn calendar(solved_days: u8) -> Calendar {
+        let days = Event::advent_of_code(year!(2023))
+            .days()
+            .map(|day| DayProgress {
+                day,
+                parts: if day.into_inner() <= solved_days {
+                    [PartProgress::Solved, PartProgress::Solved]
+                } else if day == 25 {
+                    [PartProgress::Solved, PartProgress::Started]
+                } else {
+                    [PartProgress::Answered, PartProgress::NotStarted]
+                },
+            })
+            .collect();
+
+        Calendar::new(year!(2023), days)
+    }
+
+    #[test]
+    fn counts_stars() {
+        let calendar = calendar(3);
+        assert_eq!(calendar.stars(), 7);
+        assert_eq!(calendar.max_stars(), 50);
+        assert!(calendar.render().contains("   4 ☆ "));
+    }
+
+    #[test]
+    fn awards_the_last_star() {
+        assert_eq!(calendar(24).stars(), 50);
+        assert_eq!(calendar(23).stars(), 47);
+    }
+}
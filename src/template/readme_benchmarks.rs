@@ -1,10 +1,15 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, time::Duration};
 
-use crate::Day;
+use crate::template::benchmark_export;
+use crate::template::bin_path;
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::runner::BenchStats;
+use crate::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static HISTORY_HEADING: &str = "History";
 
 #[derive(Debug)]
 pub enum Error {
@@ -18,26 +23,45 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse README: {e}"),
+            Error::IO(e) => write!(f, "could not access README: {e}"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Heap usage of the parts, only measured with the `memory` feature.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    bin_path(Year::from_env(), day)
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+    locate_markers(readme, MARKER)
+}
+
+/// Finds the section of the README between two occurrences of `marker`, including the markers.
+pub(crate) fn locate_markers(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,54 +82,287 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// Number of days that are highlighted as the slowest ones.
+const SLOWEST_DAYS: usize = 3;
+
+/// A previous benchmark run, as listed in the history section of the table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// The `git describe` output of the benchmarked revision, if known.
+    pub revision: Option<String>,
+    pub total_millis: f64,
+    pub part_1_millis: f64,
+    pub part_2_millis: f64,
+}
+
+impl HistoryEntry {
+    fn new(timings: &[Timings], total_millis: f64, revision: Option<String>) -> Self {
+        let [part_1, part_2] = part_totals(timings);
+        Self {
+            revision,
+            total_millis,
+            part_1_millis: part_1.as_secs_f64() * 1000.0,
+            part_2_millis: part_2.as_secs_f64() * 1000.0,
+        }
+    }
+
+    fn to_row(&self, previous: Option<&Self>) -> String {
+        format!(
+            "| {} | `{:.2}ms` | `{:.2}ms` | `{:.2}ms` | {} |",
+            self.revision
+                .as_ref()
+                .map_or_else(|| "-".into(), |x| format!("`{x}`")),
+            self.total_millis,
+            self.part_1_millis,
+            self.part_2_millis,
+            previous.map_or_else(|| "-".into(), |x| trend(x.total_millis, self.total_millis))
+        )
+    }
+
+    fn from_row(row: &str) -> Option<Self> {
+        let cells: Vec<&str> = row
+            .trim()
+            .strip_prefix('|')?
+            .strip_suffix('|')?
+            .split('|')
+            .map(|x| x.trim().trim_matches('`'))
+            .collect();
+
+        let millis = |cell: &str| cell.strip_suffix("ms")?.parse::<f64>().ok();
+
+        match cells[..] {
+            [revision, total, part_1, part_2, _] => Some(Self {
+                revision: Some(revision.to_string()).filter(|x| x != "-"),
+                total_millis: millis(total)?,
+                part_1_millis: millis(part_1)?,
+                part_2_millis: millis(part_2)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// An arrow that shows how a duration changed, changes within 1% count as unchanged.
+fn trend(previous: f64, current: f64) -> String {
+    if previous <= 0.0 {
+        return "-".into();
+    }
+
+    let change = (current - previous) / previous * 100.0;
+    if change.abs() < 1.0 {
+        "→".into()
+    } else if change > 0.0 {
+        format!("↑ +{change:.1}%")
+    } else {
+        format!("↓ {change:.1}%")
+    }
+}
+
+/// Reads the history section of an existing table, returns its size and its entries (newest first).
+fn parse_history(table: &str) -> Option<(usize, Vec<HistoryEntry>)> {
+    let (_, history) = table.split_once(HISTORY_HEADING)?;
+
+    let size = history
+        .trim_start()
+        .strip_prefix("(last ")?
+        .split_once(' ')?
+        .0
+        .parse()
+        .ok()?;
+
+    let entries = history
+        .lines()
+        .skip(1)
+        .filter_map(HistoryEntry::from_row)
+        .collect();
+
+    Some((size, entries))
+}
+
+/// Sum of the cells of each part over all days, so the totals match the rounded timings in the
+/// table. Cells without a timing, e.g. of parts that timed out, are skipped.
+fn part_totals(timings: &[Timings]) -> [Duration; 2] {
+    let sum = |cell: fn(&Timings) -> Option<&String>| {
+        timings
+            .iter()
+            .filter_map(|x| cell(x).and_then(|x| parse_duration(x)))
+            .sum::<Duration>()
+    };
+
+    [sum(|x| x.part_1.as_ref()), sum(|x| x.part_2.as_ref())]
+}
+
+/// Reads a duration in the `Debug` format of [`Duration`], e.g. `74.1ms`.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_duration(s: &str) -> Option<Duration> {
+    let unit_start = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(unit_start);
+    let value: f64 = value.parse().ok()?;
+
+    let nanos_per_unit = match unit {
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(Duration::from_nanos((value * nanos_per_unit).round() as u64))
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    history: &[HistoryEntry],
+    history_size: usize,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // memory columns are only shown for runs built with the `memory` feature.
+    let has_memory = timings
+        .iter()
+        .any(|x| x.part_1_memory.is_some() || x.part_2_memory.is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |".into());
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
+
+    let memory_cell = |memory: Option<MemoryStats>| {
+        memory.map_or_else(
+            || "-".into(),
+            |x| {
+                format!(
+                    "`{}` ({} allocs)",
+                    format_bytes(x.peak_bytes),
+                    x.allocations
+                )
+            },
+        )
+    };
+
+    // highlighting is only meaningful if some days are not highlighted.
+    let mut by_duration: Vec<&Timings> = timings.iter().filter(|x| x.total_nanos > 0.0).collect();
+    by_duration.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    let slowest: Vec<Day> = if by_duration.len() > SLOWEST_DAYS {
+        by_duration[..SLOWEST_DAYS].iter().map(|x| x.day).collect()
+    } else {
+        vec![]
+    };
+
+    let [part_1_total, part_2_total] = part_totals(&timings);
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+        let emphasis = if slowest.contains(&timing.day) {
+            "**"
+        } else {
+            ""
+        };
+        let mut line = format!(
+            "| {emphasis}[Day {}]({}){emphasis} | {emphasis}`{}`{emphasis} | {emphasis}`{}`{emphasis} |",
             timing.day.into_inner(),
             path,
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+        if has_memory {
+            line.push_str(&format!(
+                " {} | {} |",
+                memory_cell(timing.part_1_memory),
+                memory_cell(timing.part_2_memory)
+            ));
+        }
+        lines.push(line);
     }
 
+    let mut totals = format!("| **Total** | `{part_1_total:.1?}` | `{part_2_total:.1?}` |");
+    if has_memory {
+        totals.push_str(" - | - |");
+    }
+    lines.push(totals);
+
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if !slowest.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("The {SLOWEST_DAYS} slowest days are highlighted."));
+    }
+
+    if history_size > 0 && !history.is_empty() {
+        lines.push(String::new());
+        lines.push(format!(
+            "{prefix}# {HISTORY_HEADING} (last {history_size} runs)"
+        ));
+        lines.push(String::new());
+        lines.push("| Revision | Total | Part 1 | Part 2 | Trend |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---: |".into());
+        for (index, entry) in history.iter().enumerate() {
+            lines.push(entry.to_row(history.get(index + 1)));
+        }
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Replaces the table between the markers. `history` is the number of runs kept in the history
+/// section, [`None`] keeps the size of an existing section, `Some(0)` removes it.
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    history: Option<usize>,
+    revision: Option<String>,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+
+    let (existing_size, mut entries) =
+        parse_history(&s[positions.pos_start..positions.pos_end]).unwrap_or_default();
+    let history_size = history.unwrap_or(existing_size);
+
+    entries.insert(0, HistoryEntry::new(&timings, total_millis, revision));
+    entries.truncate(history_size);
+
+    let table = construct_table("##", timings, total_millis, &entries, history_size);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(
+    timings: Vec<Timings>,
+    total_millis: f64,
+    history: Option<usize>,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        history,
+        benchmark_export::git_revision(),
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use std::time::Duration;
+
+    use super::{update_content, HistoryEntry, Timings, MARKER};
     use crate::day;
+    use crate::template::memory::MemoryStats;
+    use crate::template::runner::BenchStats;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -113,18 +370,30 @@ mod tests {
                 day: day!(1),
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_memory: None,
+                part_2_memory: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -134,28 +403,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +432,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, None, None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -175,6 +444,7 @@ mod tests {
             "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
             "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
             "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| **Total** | `80.0ms` | `110.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +453,90 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 3 * 1024 * 1024,
+            allocations: 120,
+        });
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None, None).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `3.0 MiB` (120 allocs) | - |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - |"));
+    }
+
+    #[test]
+    fn highlights_slowest_days_and_sums_parts() {
+        let mut timings = get_mock_timings();
+        timings.push(Timings {
+            day: day!(5),
+            part_1: Some("1.4ms".into()),
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos: 1e+6,
+        });
+        // the totals add up the rounded cells, not the exact medians.
+        timings[0].part_1_stats = Some(BenchStats {
+            median: Duration::from_micros(10_049),
+            ..BenchStats::default()
+        });
+        timings[1].part_2 = Some("timed out".into());
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None, None).unwrap();
+        assert!(s.contains("| **[Day 4](./src/bin/04.rs)** | **`40ms`** | **`50ms`** |"));
+        assert!(s.contains("| [Day 5](./src/bin/05.rs) | `1.4ms` | `-` |"));
+        assert!(s.contains("| **Total** | `81.4ms` | `70.0ms` |"));
+        assert!(s.contains("The 3 slowest days are highlighted."));
+    }
+
+    #[test]
+    fn keeps_history_of_runs() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+
+        update_content(&mut s, get_mock_timings(), 200.0, Some(2), Some("a".into())).unwrap();
+        assert!(s.contains("### History (last 2 runs)"));
+
+        // the size of the history is kept if it is not passed again.
+        update_content(&mut s, get_mock_timings(), 100.0, None, Some("b".into())).unwrap();
+        update_content(&mut s, get_mock_timings(), 100.5, None, Some("c".into())).unwrap();
+
+        assert!(s.contains("| `c` | `100.50ms` | `80.00ms` | `110.00ms` | → |"));
+        assert!(s.contains("| `b` | `100.00ms` | `80.00ms` | `110.00ms` | - |"));
+        assert!(!s.contains("`a`"));
+        assert_eq!(s.matches(MARKER).count(), 2);
+
+        update_content(&mut s, get_mock_timings(), 100.0, Some(0), None).unwrap();
+        assert!(!s.contains("History"));
+    }
+
+    #[test]
+    fn round_trips_history_rows() {
+        let entry = HistoryEntry {
+            revision: Some("abc1234-dirty".into()),
+            total_millis: 190.0,
+            part_1_millis: 80.25,
+            part_2_millis: 109.75,
+        };
+        let previous = HistoryEntry {
+            total_millis: 200.0,
+            ..entry.clone()
+        };
+
+        let row = entry.to_row(Some(&previous));
+        assert_eq!(
+            row,
+            "| `abc1234-dirty` | `190.00ms` | `80.25ms` | `109.75ms` | ↓ -5.0% |"
+        );
+        assert_eq!(HistoryEntry::from_row(&row), Some(entry));
+    }
 }
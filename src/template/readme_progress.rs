@@ -0,0 +1,116 @@
+/// Module that keeps a star calendar of the solved days in the readme.
+/// Like the benchmarks, the section lives between two markers that are replaced on every update.
+use std::fs;
+
+use crate::template::bin_path;
+use crate::template::progress::{Calendar, PartProgress};
+use crate::template::readme_benchmarks::{locate_markers, Error};
+
+pub static MARKER: &str = "<!--- progress calendar --->";
+
+fn star(progress: PartProgress) -> &'static str {
+    match progress {
+        PartProgress::Solved => "⭐",
+        PartProgress::Answered => "☆",
+        PartProgress::Started | PartProgress::NotStarted => "",
+    }
+}
+
+fn construct_section(prefix: &str, calendar: &Calendar) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("{prefix} Progress"),
+        String::new(),
+        format!("**{}/{} stars**", calendar.stars(), calendar.max_stars()),
+        String::new(),
+    ];
+
+    for (index, week) in calendar.days.chunks(5).enumerate() {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|x| {
+                let day = x.day.into_inner();
+                let label = if x.parts.iter().any(|x| *x > PartProgress::NotStarted) {
+                    format!("[Day {day}]({})", bin_path(calendar.year, x.day))
+                } else {
+                    format!("Day {day}")
+                };
+                format!("{label} {}{}", star(x.parts[0]), star(x.parts[1]))
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+
+        lines.push(format!("| {} |", cells.join(" | ")));
+
+        // the first week doubles as the header of the table.
+        if index == 0 {
+            lines.push(format!("|{}", " :---: |".repeat(week.len())));
+        }
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, calendar: &Calendar) -> Result<(), Error> {
+    let positions = locate_markers(s, MARKER)?;
+    let section = construct_section("##", calendar);
+    s.replace_range(positions.pos_start..positions.pos_end, &section);
+    Ok(())
+}
+
+pub fn update(calendar: &Calendar) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, calendar)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, MARKER};
+    use crate::template::progress::{Calendar, DayProgress, PartProgress};
+    use crate::{day, year};
+
+    #[test]
+    fn formats_progress() {
+        let calendar = Calendar::new(
+            year!(2023),
+            vec![
+                DayProgress {
+                    day: day!(1),
+                    parts: [PartProgress::Solved, PartProgress::Solved],
+                },
+                DayProgress {
+                    day: day!(2),
+                    parts: [PartProgress::Solved, PartProgress::Answered],
+                },
+                DayProgress {
+                    day: day!(3),
+                    parts: [PartProgress::NotStarted, PartProgress::NotStarted],
+                },
+            ],
+        );
+
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        update_content(&mut s, &calendar).unwrap();
+        update_content(&mut s, &calendar).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "## Progress",
+            "",
+            "**3/6 stars**",
+            "",
+            "| [Day 1](./src/bin/01.rs) ⭐⭐ | [Day 2](./src/bin/02.rs) ⭐☆ | Day 3 |",
+            "| :---: | :---: | :---: |",
+            MARKER,
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
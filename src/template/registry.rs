@@ -0,0 +1,101 @@
+/// In-process registry of every scaffolded solution.
+/// Each `src/bin/DD.rs` and `src/bin/YYYY-DD.rs` is compiled into the library by `build.rs`, and the `solution!` macro
+/// exposes its parts as a [`Solution`], so commands can call them without spawning `cargo`.
+/// A day can have further named variants, e.g. a naive reference next to an optimised solution, in files named
+/// `DD-<variant>.rs` or `YYYY-DD-<variant>.rs`.
+use crate::solutions::Answer;
+use crate::template::runner::{PartRun, RunConfig};
+use crate::template::{bin_name, input::Shape};
+use crate::{Day, Event, Year};
+
+/// Runs one part against an input.
+pub type PartFn = fn(&str, &RunConfig) -> PartRun;
+
+/// Solves one part of an input, returning the typed answer (see [`crate::solutions`]).
+pub type AnswerFn = fn(&str) -> Result<Option<Answer>, String>;
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    /// The event of the solution, [`None`] for the default year (see [`Year::from_env`]).
+    pub year: Option<Year>,
+    pub day: Day,
+    /// The name of the variant, [`None`] for the main solution of the day.
+    pub variant: Option<&'static str>,
+    /// The layout of the puzzle input, [`None`] if the day does not declare one.
+    pub shape: Option<Shape>,
+    /// A regex every line of the puzzle input that is not blank matches, see [`crate::template::input::validate`].
+    pub pattern: Option<&'static str>,
+    pub part_one: PartFn,
+    pub part_two: PartFn,
+    pub answer_one: AnswerFn,
+    pub answer_two: AnswerFn,
+}
+
+impl Solution {
+    #[must_use]
+    pub fn year(&self) -> Year {
+        self.year.unwrap_or_else(Year::from_env)
+    }
+
+    /// The number of parts of the day, see [`Event::parts`].
+    #[must_use]
+    pub fn parts(&self) -> u8 {
+        Event::for_year(self.year()).parts(self.day)
+    }
+
+    /// The name of the variant, `main` for the main solution of the day.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.variant.unwrap_or("main")
+    }
+
+    /// Name of the binary that runs this variant, e.g. `10-softwaregore`.
+    #[must_use]
+    pub fn bin_name(&self) -> String {
+        let bin_name = bin_name(self.year(), self.day);
+        match self.variant {
+            Some(variant) => format!("{bin_name}-{variant}"),
+            None => bin_name,
+        }
+    }
+
+    /// Runs the given part (`1` or `2`), returns [`None`] for any other part number.
+    #[must_use]
+    pub fn run_part(&self, part: u8, input: &str, config: &RunConfig) -> Option<PartRun> {
+        match part {
+            1 => Some((self.part_one)(input, config)),
+            2 => Some((self.part_two)(input, config)),
+            _ => None,
+        }
+    }
+}
+
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// Every registered solution, ordered by year and day.
+#[must_use]
+pub fn solutions() -> &'static [Solution] {
+    days::SOLUTIONS
+}
+
+/// The registered main solution for a day of an event, if it has been scaffolded.
+#[must_use]
+pub fn get(year: Year, day: Day) -> Option<&'static Solution> {
+    variants(year, day)
+        .into_iter()
+        .find(|x| x.variant.is_none())
+}
+
+/// Every registered variant of a day, the main solution first and the others ordered by name.
+#[must_use]
+pub fn variants(year: Year, day: Day) -> Vec<&'static Solution> {
+    let mut variants: Vec<&'static Solution> = solutions()
+        .iter()
+        .filter(|x| x.year() == year && x.day == day)
+        .collect();
+
+    variants.sort_by_key(|x| x.variant);
+    variants
+}
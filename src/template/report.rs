@@ -0,0 +1,177 @@
+/// Module for machine-readable results of solution runs, see `--format json`.
+/// Every part is reported as one JSON object per line, so tools can read results without parsing
+/// the decorated terminal output.
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::runner::PartRun;
+use crate::{Day, Year};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output with colors, the default.
+    #[default]
+    Text,
+    /// One [`PartReport`] per line.
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("expected `text` or `json` as format, got {s:?}")),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Reads `--format` from a list of command-line arguments, falling back to text.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        match args.iter().position(|x| x == "--format") {
+            Some(index) => args
+                .get(index + 1)
+                .ok_or_else(|| "expected a value after --format".to_string())?
+                .parse(),
+            None => Ok(Self::Text),
+        }
+    }
+
+    /// Turns the format back into flags so it can be forwarded to the solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Text => vec![],
+            Self::Json => vec!["--format".into(), "json".into()],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// The part produced an answer.
+    Solved,
+    /// The part is not solved yet, i.e. it returned `None`.
+    None,
+    /// The part returned an error, panicked, timed out or its input could not be read.
+    Error,
+}
+
+/// The result of a single part. Durations are the median of the recorded samples, in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The variant of the day that produced the result, omitted for the main solution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub nanos: u64,
+    pub samples: u64,
+}
+
+impl PartReport {
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(year: Year, day: Day, part: u8, run: &PartRun) -> Self {
+        let status = match (&run.answer, &run.error) {
+            (_, Some(_)) => Status::Error,
+            (Some(_), None) => Status::Solved,
+            (None, None) => Status::None,
+        };
+
+        Self {
+            year: year.into_inner(),
+            day: day.into_inner(),
+            part,
+            variant: None,
+            status,
+            answer: run.answer.clone(),
+            error: run.error.clone(),
+            nanos: run.stats.median.as_nanos() as u64,
+            samples: run.stats.samples as u64,
+        }
+    }
+
+    /// A part that did not return, e.g. because it panicked.
+    #[must_use]
+    pub fn failed(year: Year, day: Day, part: u8, error: impl Into<String>) -> Self {
+        Self {
+            year: year.into_inner(),
+            day: day.into_inner(),
+            part,
+            variant: None,
+            status: Status::Error,
+            answer: None,
+            error: Some(error.into()),
+            nanos: 0,
+            samples: 0,
+        }
+    }
+
+    /// Attributes the report to a variant of the day, see [`Solution::variant`](crate::template::registry::Solution::variant).
+    #[must_use]
+    pub fn with_variant(mut self, variant: Option<&str>) -> Self {
+        self.variant = variant.map(Into::into);
+        self
+    }
+
+    /// Prints the report as a single line of JSON.
+    pub fn print(&self) {
+        match serde_json::to_string(self) {
+            Ok(json) => println!("{json}"),
+            Err(e) => eprintln!("Failed to serialize the result of part {}: {e}", self.part),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{OutputFormat, PartReport};
+    use crate::template::runner::{BenchStats, PartRun};
+    use crate::{day, year};
+
+    #[test]
+    fn serializes_part_reports() {
+        let run = PartRun {
+            answer: Some("35".into()),
+            error: None,
+            stats: BenchStats::single(Duration::from_micros(12)),
+            memory: None,
+        };
+
+        let report = PartReport::new(year!(2023), day!(5), 1, &run);
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"year":2023,"day":5,"part":1,"status":"solved","answer":"35","error":null,"nanos":12000,"samples":1}"#
+        );
+
+        let report = PartReport::failed(year!(2023), day!(5), 2, "panicked");
+        assert!(serde_json::to_string(&report)
+            .unwrap()
+            .contains(r#""status":"error","answer":null,"error":"panicked""#));
+
+        let report = report.with_variant(Some("naive"));
+        assert!(serde_json::to_string(&report)
+            .unwrap()
+            .contains(r#""part":2,"variant":"naive","status""#));
+    }
+
+    #[test]
+    fn round_trips_format_through_args() {
+        for format in [OutputFormat::Text, OutputFormat::Json] {
+            assert_eq!(OutputFormat::from_args(&format.to_args()), Ok(format));
+        }
+        assert!(OutputFormat::from_args(&["--format".into(), "yaml".into()]).is_err());
+    }
+}
@@ -1,63 +1,449 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::answers;
+use crate::template::input::InputSource;
+use crate::template::memory::{self, MemoryStats};
+use crate::template::registry::PartFn;
+use crate::template::report::{OutputFormat, PartReport};
+use crate::template::submissions::{self, Ledger, Verdict};
+use crate::template::{aoc_client, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+use std::any::Any;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::path::Path;
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Return types accepted from `part_one` and `part_two`: `Option<T>`, where `None` means that
+/// the part is not solved, or `Result<T, E>` for solutions that explain why an input is invalid.
+pub trait PartOutput {
+    /// The formatted answer, or the formatted error.
+    fn answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+) {
+    let args: Vec<String> = env::args().collect();
+
+    let bench_config = args.iter().any(|x| x == "--time").then(|| {
+        BenchConfig::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("Invalid bench configuration: {e}");
+            process::exit(1);
+        })
+    });
+
+    let format = OutputFormat::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Invalid output format: {e}");
+        process::exit(1);
+    });
+
+    let config = RunConfig {
+        bench: bench_config,
+        quiet: format == OutputFormat::Json,
+    };
+
+    let run = measure_part(func, input, part, &config);
+
+    if format == OutputFormat::Json {
+        PartReport::new(year, day, part, &run).print();
+    }
+
+    if let Some(answer) = run.answer {
+        submit_result(answer, year, day, part);
+    }
+}
+
+/// The answer and timings of a solution part, with the answer already formatted for display.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartRun {
+    pub answer: Option<String>,
+    /// The error returned by a `Result` part, e.g. a [`crate::ParseError`] diagnostic.
+    pub error: Option<String>,
+    pub stats: BenchStats,
+    /// Heap usage of the first run, only measured with the `memory` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+/// Controls how [`measure_part`] executes a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    /// Benches the part with this config, runs it once otherwise.
+    pub bench: Option<BenchConfig>,
+    /// Suppresses all output, so the caller can print the [`PartRun`] later via [`print_part_run`].
+    pub quiet: bool,
+}
+
+/// Run a solution part, print its result and return it.
+pub fn measure_part<I: Clone, O: PartOutput>(
+    func: impl Fn(I) -> O,
+    input: I,
+    part: u8,
+    config: &RunConfig,
+) -> PartRun {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, memory) = run_timed(
+        func,
+        input,
+        |result| {
+            if !config.quiet {
+                print_result(&result.answer().unwrap_or_default(), &part_str, "");
+            }
+        },
+        config.bench.as_ref(),
+        config.quiet,
+    );
+
+    let (answer, error) = match result.answer() {
+        Ok(answer) => (answer, None),
+        Err(e) => (None, Some(e)),
+    };
+
+    let run = PartRun {
+        answer,
+        error,
+        stats,
+        memory,
+    };
+
+    if !config.quiet {
+        print_part_run(&run, part);
+    }
+
+    run
+}
+
+/// Prints the final result line of a part, e.g. `Part 1: 42 (1.2ms)`, followed by its error if it failed.
+pub fn print_part_run(run: &PartRun, part: u8) {
+    let mut duration_str = format_duration(&run.stats);
+    if let Some(memory) = &run.memory {
+        duration_str.push_str(&format!(" [{memory}]"));
+    }
+
+    print_result(&run.answer, &format!("Part {part}"), &duration_str);
+
+    if let Some(error) = &run.error {
+        eprintln!("{}", error.trim_end());
+    }
+}
+
+/// How a part that was run in isolation ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum PartOutcome {
+    /// The part returned, with or without an answer.
+    Finished(PartRun),
+    TimedOut(Duration),
+    /// The part panicked, with the panic message.
+    Panicked(String),
+}
+
+impl PartOutcome {
+    pub(crate) fn print(&self, part: u8) {
+        match self {
+            PartOutcome::Finished(run) => print_part_run(run, part),
+            PartOutcome::TimedOut(timeout) => {
+                println!("Part {part}: ✖ {ANSI_ITALIC}timed out after {timeout:.1?}{ANSI_RESET}");
+            }
+            PartOutcome::Panicked(message) => {
+                println!("Part {part}: ✖ {ANSI_ITALIC}panicked: {message}{ANSI_RESET}");
+            }
+        }
+    }
+
+    pub(crate) fn report(&self, year: Year, day: Day, part: u8) -> PartReport {
+        match self {
+            PartOutcome::Finished(run) => PartReport::new(year, day, part, run),
+            PartOutcome::TimedOut(timeout) => {
+                PartReport::failed(year, day, part, format!("timed out after {timeout:.1?}"))
+            }
+            PartOutcome::Panicked(message) => {
+                PartReport::failed(year, day, part, format!("panicked: {message}"))
+            }
+        }
+    }
+
+    /// The cell of the part in the benchmark table, if the part did not produce an answer.
+    pub(crate) fn marker(&self) -> Option<&'static str> {
+        match self {
+            PartOutcome::Finished(_) => None,
+            PartOutcome::TimedOut(_) => Some("timed out"),
+            PartOutcome::Panicked(_) => Some("panicked"),
+        }
+    }
+}
+
+/// Runs `func` on a new thread and waits for it at most `timeout`.
+/// A part that timed out can not be stopped, its thread keeps running until the process exits.
+pub(crate) fn isolate(
+    func: PartFn,
+    input: Arc<str>,
+    config: RunConfig,
+    timeout: Option<Duration>,
+) -> PartOutcome {
+    let (tx, rx) = mpsc::channel();
+
+    let handle = thread::spawn(move || {
+        // the receiver is gone if the part timed out, nobody is interested in the result anymore.
+        let _ = tx.send(func(&input, &config));
+    });
+
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(mpsc::RecvTimeoutError::from),
+    };
+
+    match received {
+        Ok(run) => PartOutcome::Finished(run),
+        Err(mpsc::RecvTimeoutError::Timeout) => PartOutcome::TimedOut(timeout.unwrap_or_default()),
+        // the sender was dropped without sending, i.e. the thread unwound.
+        Err(mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => PartOutcome::Panicked(panic_message(payload.as_ref())),
+            Ok(()) => PartOutcome::Panicked("the part exited without a result".into()),
+        },
+    }
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into())
+}
+
+/// Sampling budget used when benching a solution part.
+///
+/// The defaults mirror the original behavior: roughly one second of samples, clamped to 10..=10000 runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Time spent running the function before samples are recorded.
+    pub warmup: Duration,
+    /// Approximate time spent recording samples.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::from_millis(100),
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// The flags read by [`BenchConfig::from_args`], each followed by a number.
+    pub const FLAGS: [&'static str; 4] = [
+        "--bench-warmup",
+        "--bench-time",
+        "--bench-min-samples",
+        "--bench-max-samples",
+    ];
+
+    /// Reads the `--bench-*` flags from a list of command-line arguments, falling back to the defaults.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut config = Self::default();
+
+        if let Some(ms) = parse_flag::<u64>(args, "--bench-warmup")? {
+            config.warmup = Duration::from_millis(ms);
+        }
+        if let Some(ms) = parse_flag::<u64>(args, "--bench-time")? {
+            config.budget = Duration::from_millis(ms);
+        }
+        if let Some(n) = parse_flag(args, "--bench-min-samples")? {
+            config.min_samples = n;
+        }
+        if let Some(n) = parse_flag(args, "--bench-max-samples")? {
+            config.max_samples = n;
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Turns the config back into `--bench-*` flags so it can be mirrored to child invocations.
+    /// Values equal to the default are omitted.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let default = Self::default();
+        let mut args = vec![];
+
+        if self.warmup != default.warmup {
+            args.push("--bench-warmup".into());
+            args.push(self.warmup.as_millis().to_string());
+        }
+        if self.budget != default.budget {
+            args.push("--bench-time".into());
+            args.push(self.budget.as_millis().to_string());
+        }
+        if self.min_samples != default.min_samples {
+            args.push("--bench-min-samples".into());
+            args.push(self.min_samples.to_string());
+        }
+        if self.max_samples != default.max_samples {
+            args.push("--bench-max-samples".into());
+            args.push(self.max_samples.to_string());
+        }
+
+        args
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 {
+            return Err("--bench-min-samples must be at least 1".into());
+        }
+        if self.min_samples > self.max_samples {
+            return Err(format!(
+                "--bench-min-samples ({}) must not exceed --bench-max-samples ({})",
+                self.min_samples, self.max_samples
+            ));
+        }
+        Ok(())
+    }
+
+    /// Number of samples to record for a function that took `base_time` to run once.
+    fn sample_count(&self, base_time: &Duration) -> u128 {
+        (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+fn parse_flag<T: std::str::FromStr>(args: &[String], flag: &str) -> Result<Option<T>, String> {
+    let Some(index) = args.iter().position(|x| x == flag) else {
+        return Ok(None);
+    };
+
+    args.get(index + 1)
+        .and_then(|value| value.parse().ok())
+        .map(Some)
+        .ok_or_else(|| format!("expected a number after {flag}"))
+}
+
+/// Summary statistics over the samples recorded for a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+}
+
+impl BenchStats {
+    /// Stats for a function that was executed exactly once.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self::from_samples(&[duration])
+    }
+
+    /// Computes the stats for a non-empty set of samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot compute stats without samples");
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let len = sorted.len();
+        let nanos: Vec<f64> = sorted.iter().map(|x| x.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / len as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len as f64;
+
+        // both indices are the middle sample for an odd number of samples.
+        let median = (sorted[(len - 1) / 2] + sorted[len / 2]) / 2;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        // nearest-rank percentile.
+        let p95_rank = ((len as f64) * 0.95).ceil() as usize;
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+        Self {
+            samples: len as u128,
+            min: sorted[0],
+            median,
+            mean: Duration::from_secs_f64(mean / 1e9),
+            p95: sorted[p95_rank.saturating_sub(1)],
+            max: sorted[len - 1],
+            std_dev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+        }
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run a solution part. The behavior differs depending on whether a [`BenchConfig`] is passed:
+///  1. without one, the function is executed once.
+///  2. with one, the function is benched according to the config (see the `--bench-*` flags).
+///
+/// Heap usage is measured for the first execution only.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    bench_config: Option<&BenchConfig>,
+    quiet: bool,
+) -> (T, BenchStats, Option<MemoryStats>) {
+    let cloned = input.clone();
+    let ((result, base_time), memory) = memory::measure(|| {
+        let timer = Instant::now();
+        let result = func(cloned);
+        (result, timer.elapsed())
+    });
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let stats = match bench_config {
+        Some(config) => bench(func, input, &base_time, config, quiet),
+        None => BenchStats::single(base_time),
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    quiet: bool,
+) -> BenchStats {
+    if !quiet {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // warm caches and branch predictors before recording any samples.
+    let warmup = Instant::now();
+    while warmup.elapsed() < config.warmup {
+        func(input.clone());
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations = config.sample_count(base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,26 +455,27 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    BenchStats::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Formats the stats as they are printed next to a result, e.g. `(1.2ms @ 100 samples) [min 1.1ms, ...]`.
+/// The headline value is the median, which is less sensitive to outliers than the mean.
+#[must_use]
+pub fn format_duration(stats: &BenchStats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({:.1?} @ {} samples) [min {:.1?}, median {:.1?}, mean {:.1?}, p95 {:.1?}, max {:.1?}, σ {:.1?}]",
+            stats.median,
+            stats.samples,
+            stats.min,
+            stats.median,
+            stats.mean,
+            stats.p95,
+            stats.max,
+            stats.std_dev
+        )
     }
 }
 
@@ -127,14 +514,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Parse the arguments passed to `solve` and try to submit one part of the solution if `--submit` is set.
 fn submit_result<T: Display>(
     result: T,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<(), aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +543,127 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if InputSource::from_args(&args) != Ok(InputSource::Puzzle) {
+        eprintln!("Refusing to submit: the answer was not computed from the puzzle input.");
+        return None;
+    }
+
+    let client = match aoc_client::AocClient::for_year(year) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Submission failed: {e}");
+            return Some(Err(e));
+        }
+    };
+
+    let mut ledger = Ledger::load(Path::new(submissions::LEDGER_PATH)).unwrap_or_else(|e| {
+        eprintln!("{e}");
         process::exit(1);
+    });
+
+    let answer = result.to_string();
+
+    if let Err(refusal) = ledger.check(client.year().into_inner(), day, part, &answer) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
+        return None;
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    let submission = client.submit(day, part, &answer);
+
+    let verdict = Verdict::from_submission(&submission);
+    ledger.record(client.year().into_inner(), day, part, &answer, verdict);
+    if let Err(e) = ledger.save() {
+        eprintln!("Failed to record submission: {e}");
+    }
+
+    match &submission {
+        Ok(()) => {
+            println!("🎄 That's the right answer!");
+            if let Err(e) = answers::record(year, day, part, &answer) {
+                eprintln!("Failed to record the answer for `cargo verify`: {e}");
+            }
+        }
+        Err(e) => eprintln!("Submission failed: {e}"),
+    }
+
+    Some(submission)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{isolate, BenchConfig, BenchStats, PartOutcome, PartRun, RunConfig};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn stats_from_samples() {
+        let samples: Vec<Duration> = [5, 1, 4, 2, 3, 100]
+            .iter()
+            .map(|x| Duration::from_millis(*x))
+            .collect();
+        let stats = BenchStats::from_samples(&samples);
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(3500));
+        assert_eq!(stats.mean.as_micros(), 19166);
+        assert_eq!(stats.p95, Duration::from_millis(100));
+        assert_eq!(stats.max, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn config_round_trips_through_args() {
+        let config = BenchConfig {
+            budget: Duration::from_millis(250),
+            max_samples: 50,
+            ..BenchConfig::default()
+        };
+        let args = config.to_args();
+        assert_eq!(args, ["--bench-time", "250", "--bench-max-samples", "50"]);
+        assert_eq!(BenchConfig::from_args(&args).unwrap(), config);
+    }
+
+    #[test]
+    fn config_rejects_inverted_sample_range() {
+        let args: Vec<String> = ["--bench-min-samples", "20", "--bench-max-samples", "10"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert!(BenchConfig::from_args(&args).is_err());
+    }
+
+    #[test]
+    fn isolates_panics_and_timeouts() {
+        let input: Arc<str> = "input".into();
+        let config = RunConfig {
+            bench: None,
+            quiet: true,
+        };
+
+        let answers = |input: &str, _: &RunConfig| PartRun {
+            answer: Some(input.len().to_string()),
+            error: None,
+            stats: BenchStats::default(),
+            memory: None,
+        };
+        let panics = |_: &str, _: &RunConfig| -> PartRun { panic!("invalid input") };
+        let hangs = |_: &str, _: &RunConfig| -> PartRun {
+            std::thread::sleep(Duration::from_secs(60));
+            unreachable!()
+        };
+
+        let timeout = Some(Duration::from_millis(50));
+        assert_eq!(
+            isolate(answers, Arc::clone(&input), config, timeout),
+            PartOutcome::Finished(answers("input", &config))
+        );
+        assert_eq!(
+            isolate(panics, Arc::clone(&input), config, None),
+            PartOutcome::Panicked("invalid input".into())
+        );
+        assert_eq!(
+            isolate(hangs, input, config, timeout),
+            PartOutcome::TimedOut(Duration::from_millis(50))
+        );
+    }
 }
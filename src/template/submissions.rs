@@ -0,0 +1,275 @@
+/// Module that keeps a local ledger of every submitted answer.
+/// Verdicts from earlier submissions are used to refuse answers that are known to be wrong,
+/// so they never reach the server and trigger a lockout.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::aoc_client::{AnswerHint, AocClientError};
+use crate::Day;
+
+pub const LEDGER_PATH: &str = "data/submissions.json";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse submission ledger: {e}"),
+            Error::IO(e) => write!(f, "could not access submission ledger: {e}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// The server did not judge the answer, e.g. because of rate limiting.
+    NotJudged,
+}
+
+impl Verdict {
+    /// Maps the outcome of a submission to a verdict.
+    #[must_use]
+    pub fn from_submission(result: &Result<(), AocClientError>) -> Self {
+        match result {
+            Ok(()) => Verdict::Correct,
+            Err(AocClientError::WrongAnswer(Some(AnswerHint::TooHigh))) => Verdict::TooHigh,
+            Err(AocClientError::WrongAnswer(Some(AnswerHint::TooLow))) => Verdict::TooLow,
+            Err(AocClientError::WrongAnswer(None)) => Verdict::Incorrect,
+            Err(_) => Verdict::NotJudged,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reason why an answer is not sent to the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    KnownWrong,
+    TooLow { lower_bound: i128 },
+    TooHigh { upper_bound: i128 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong => write!(f, "this answer was already submitted and is wrong."),
+            Refusal::TooLow { lower_bound } => {
+                write!(f, "the answer must be greater than {lower_bound}.")
+            }
+            Refusal::TooHigh { upper_bound } => {
+                write!(f, "the answer must be less than {upper_bound}.")
+            }
+        }
+    }
+}
+
+pub struct Ledger {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl Ledger {
+    /// Loads the ledger from a file. A missing file is treated as an empty ledger.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let submissions = match fs::read_to_string(path) {
+            Ok(content) => {
+                serde_json::from_str(&content).map_err(|e| Error::Parser(e.to_string()))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(&self.submissions)
+            .map_err(|e| Error::Parser(e.to_string()))?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    pub fn record(&mut self, year: u16, day: Day, part: u8, answer: &str, verdict: Verdict) {
+        self.submissions.push(Submission {
+            year,
+            day: day.into_inner(),
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+        });
+    }
+
+    fn for_part(&self, year: u16, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |x| x.year == year && x.day == day.into_inner() && x.part == part)
+    }
+
+    /// The accepted answer of a part, if it was solved.
+    #[must_use]
+    pub fn solution(&self, year: u16, day: Day, part: u8) -> Option<&str> {
+        self.for_part(year, day, part)
+            .find(|x| x.verdict == Verdict::Correct)
+            .map(|x| x.answer.as_str())
+    }
+
+    /// The exclusive bounds of a numeric answer that follow from "too low" and "too high" verdicts.
+    #[must_use]
+    pub fn bounds(&self, year: u16, day: Day, part: u8) -> (Option<i128>, Option<i128>) {
+        let numeric = |verdict: Verdict| {
+            self.for_part(year, day, part)
+                .filter(move |x| x.verdict == verdict)
+                .filter_map(|x| x.answer.trim().parse::<i128>().ok())
+        };
+
+        (
+            numeric(Verdict::TooLow).max(),
+            numeric(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// Checks an answer against earlier verdicts, returning why it should not be submitted.
+    pub fn check(&self, year: u16, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let answer = answer.trim();
+
+        if let Some(solution) = self.solution(year, day, part) {
+            return Err(Refusal::AlreadySolved {
+                answer: solution.to_string(),
+            });
+        }
+
+        let is_known_wrong = self.for_part(year, day, part).any(|x| {
+            x.answer.trim() == answer
+                && matches!(
+                    x.verdict,
+                    Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+                )
+        });
+
+        if is_known_wrong {
+            return Err(Refusal::KnownWrong);
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let (lower_bound, upper_bound) = self.bounds(year, day, part);
+
+            if let Some(lower_bound) = lower_bound.filter(|x| value <= *x) {
+                return Err(Refusal::TooLow { lower_bound });
+            }
+
+            if let Some(upper_bound) = upper_bound.filter(|x| value >= *x) {
+                return Err(Refusal::TooHigh { upper_bound });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{Ledger, Refusal, Verdict};
+    use crate::day;
+
+    fn get_mock_ledger() -> Ledger {
+        let mut ledger = Ledger::load(Path::new("data/does-not-exist.json")).unwrap();
+        ledger.record(2023, day!(1), 1, "100", Verdict::TooLow);
+        ledger.record(2023, day!(1), 1, "500", Verdict::TooHigh);
+        ledger.record(2023, day!(1), 1, "300", Verdict::TooHigh);
+        ledger.record(2023, day!(1), 1, "abc", Verdict::Incorrect);
+        ledger.record(2023, day!(1), 1, "250", Verdict::NotJudged);
+        ledger.record(2023, day!(2), 1, "42", Verdict::Correct);
+        ledger
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(2023, day!(1), 1, "abc"),
+            Err(Refusal::KnownWrong)
+        );
+        assert_eq!(
+            ledger.check(2023, day!(1), 1, "100"),
+            Err(Refusal::KnownWrong)
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let ledger = get_mock_ledger();
+        assert_eq!(ledger.bounds(2023, day!(1), 1), (Some(100), Some(300)));
+        assert_eq!(
+            ledger.check(2023, day!(1), 1, "99"),
+            Err(Refusal::TooLow { lower_bound: 100 })
+        );
+        assert_eq!(
+            ledger.check(2023, day!(1), 1, "400"),
+            Err(Refusal::TooHigh { upper_bound: 300 })
+        );
+        assert_eq!(ledger.check(2023, day!(1), 1, "250"), Ok(()));
+        assert_eq!(ledger.check(2023, day!(1), 2, "99"), Ok(()));
+        assert_eq!(ledger.check(2022, day!(1), 1, "99"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = get_mock_ledger();
+        assert_eq!(
+            ledger.check(2023, day!(2), 1, "43"),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+}
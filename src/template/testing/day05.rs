@@ -0,0 +1,99 @@
+/// Almanacs of day 5: seed ranges and maps of non-overlapping source ranges.
+use super::Rng;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// A map entry, `(destination, source, length)`.
+type Entry = (u64, u64, u64);
+
+/// Generates an almanac with small numbers, so every seed of every range can be mapped.
+#[must_use]
+pub fn generate(rng: &mut Rng) -> String {
+    let seeds: Vec<String> = (0..rng.range(1, 3))
+        .map(|_| format!("{} {}", rng.range(0, 100), rng.range(1, 15)))
+        .collect();
+
+    let mut lines = vec![format!("seeds: {}", seeds.join(" "))];
+
+    for name in &MAPS[..rng.range(1, MAPS.len())] {
+        lines.push(String::new());
+        lines.push(format!("{name} map:"));
+
+        // source ranges of a map never overlap, ranges are placed left to right with gaps.
+        let mut source = rng.range(0, 20);
+        for _ in 0..rng.range(1, 4) {
+            let length = rng.range(1, 25);
+            lines.push(format!("{} {source} {length}", rng.range(0, 120)));
+            source += length + rng.range(0, 10);
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn parse(input: &str) -> (Vec<u64>, Vec<Vec<Entry>>) {
+    let mut sections = input.split("\n\n");
+
+    let seeds = sections
+        .next()
+        .and_then(|x| x.strip_prefix("seeds:"))
+        .expect("almanac should start with seeds")
+        .split_whitespace()
+        .map(|x| x.parse().unwrap())
+        .collect();
+
+    let maps = sections
+        .map(|section| {
+            section
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let numbers: Vec<u64> = line
+                        .split_whitespace()
+                        .map(|x| x.parse().unwrap())
+                        .collect();
+                    (numbers[0], numbers[1], numbers[2])
+                })
+                .collect()
+        })
+        .collect();
+
+    (seeds, maps)
+}
+
+fn location(seed: u64, maps: &[Vec<Entry>]) -> u64 {
+    maps.iter().fold(seed, |value, map| {
+        map.iter()
+            .find(|(_, source, length)| (*source..source + length).contains(&value))
+            .map_or(value, |(destination, source, _)| {
+                destination + value - source
+            })
+    })
+}
+
+/// The lowest location of any seed.
+#[must_use]
+pub fn part_one(input: &str) -> u64 {
+    let (seeds, maps) = parse(input);
+    seeds.iter().map(|x| location(*x, &maps)).min().unwrap()
+}
+
+/// The lowest location of any seed, mapping every seed of every seed range one by one.
+#[must_use]
+pub fn part_two(input: &str) -> u64 {
+    let (seeds, maps) = parse(input);
+    seeds
+        .chunks(2)
+        .flat_map(|x| x[0]..x[0] + x[1])
+        .map(|x| location(x, &maps))
+        .min()
+        .unwrap()
+}
@@ -0,0 +1,138 @@
+/// Snapshots of falling bricks of day 22, e.g. `1,0,1~1,2,1`.
+use std::collections::HashSet;
+
+use super::Rng;
+
+type Cube = (usize, usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Brick {
+    start: Cube,
+    end: Cube,
+}
+
+impl Brick {
+    fn cubes(self) -> impl Iterator<Item = Cube> {
+        let (start, end) = (self.start, self.end);
+        (start.0..=end.0).flat_map(move |x| {
+            (start.1..=end.1).flat_map(move |y| (start.2..=end.2).map(move |z| (x, y, z)))
+        })
+    }
+
+    fn lowered(self) -> Self {
+        Self {
+            start: (self.start.0, self.start.1, self.start.2 - 1),
+            end: (self.end.0, self.end.1, self.end.2 - 1),
+        }
+    }
+}
+
+/// Generates a few bricks that do not overlap in a small area, so every brick can be removed and
+/// the rest dropped cube by cube.
+#[must_use]
+pub fn generate(rng: &mut Rng) -> String {
+    let mut bricks: Vec<Brick> = vec![];
+    let mut occupied: HashSet<Cube> = HashSet::new();
+
+    for _ in 0..rng.range(1, 10) {
+        let start = (rng.range(0, 3), rng.range(0, 3), rng.range(1, 12));
+        let mut end = start;
+        match rng.range(0, 2) {
+            0 => end.0 = (start.0 + rng.range(0, 2)).min(3),
+            1 => end.1 = (start.1 + rng.range(0, 2)).min(3),
+            _ => end.2 = start.2 + rng.range(0, 2),
+        }
+
+        let brick = Brick { start, end };
+        if brick.cubes().all(|x| !occupied.contains(&x)) {
+            occupied.extend(brick.cubes());
+            bricks.push(brick);
+        }
+    }
+
+    bricks
+        .iter()
+        .map(|Brick { start, end }| {
+            format!(
+                "{},{},{}~{},{},{}\n",
+                start.0, start.1, start.2, end.0, end.1, end.2
+            )
+        })
+        .collect()
+}
+
+fn parse(input: &str) -> Vec<Brick> {
+    input
+        .lines()
+        .map(|line| {
+            let cube = |s: &str| {
+                let coordinates: Vec<usize> = s.split(',').map(|x| x.parse().unwrap()).collect();
+                (coordinates[0], coordinates[1], coordinates[2])
+            };
+            let (start, end) = line.split_once('~').expect("brick should have two ends");
+            Brick {
+                start: cube(start),
+                end: cube(end),
+            }
+        })
+        .collect()
+}
+
+/// Lowers bricks one step at a time until none of them can fall, returns how many bricks fell.
+fn settle(bricks: &mut [Brick]) -> usize {
+    let mut fallen = vec![false; bricks.len()];
+
+    loop {
+        let mut has_moved = false;
+
+        for index in 0..bricks.len() {
+            let brick = bricks[index];
+            if brick.start.2 == 1 {
+                continue;
+            }
+
+            let occupied: HashSet<Cube> = bricks
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .flat_map(|(_, x)| x.cubes())
+                .collect();
+
+            if brick.lowered().cubes().all(|x| !occupied.contains(&x)) {
+                bricks[index] = brick.lowered();
+                fallen[index] = true;
+                has_moved = true;
+            }
+        }
+
+        if !has_moved {
+            return fallen.iter().filter(|x| **x).count();
+        }
+    }
+}
+
+/// How many bricks fall once each of the settled bricks is disintegrated.
+fn falling_bricks(input: &str) -> Vec<usize> {
+    let mut bricks = parse(input);
+    settle(&mut bricks);
+
+    (0..bricks.len())
+        .map(|index| {
+            let mut rest = bricks.clone();
+            rest.remove(index);
+            settle(&mut rest)
+        })
+        .collect()
+}
+
+/// The number of bricks that can be disintegrated without any other brick falling.
+#[must_use]
+pub fn part_one(input: &str) -> u64 {
+    falling_bricks(input).iter().filter(|x| **x == 0).count() as u64
+}
+
+/// The sum of the bricks that would fall for each disintegrated brick.
+#[must_use]
+pub fn part_two(input: &str) -> u64 {
+    falling_bricks(input).iter().sum::<usize>() as u64
+}
@@ -0,0 +1,123 @@
+/// Module with support for property tests of solutions.
+/// Puzzle inputs can not be committed and the examples rarely cover edge cases, so the submodules
+/// generate random inputs in the format of a puzzle and solve them with naive reference solvers.
+/// A solution is checked by comparing its answers with the reference on many generated inputs:
+///
+/// ```ignore
+/// testing::check(1000, day05::generate, |input| {
+///     assert_eq!(part_one(input), Some(day05::part_one(input)));
+/// });
+/// ```
+///
+/// The module is only compiled for tests, run them with `cargo test --features test_lib`.
+use std::{
+    env,
+    panic::{self, RefUnwindSafe},
+};
+
+pub mod day05;
+pub mod day22;
+
+/// Seed of the first case, can be overridden with `AOC_PROPERTY_SEED` to reproduce a failure.
+const DEFAULT_SEED: u64 = 2023;
+
+/// A small seeded random number generator (`SplitMix64`), so failing cases can be reproduced.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the inclusive range `min..=max`.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn range(&mut self, min: usize, max: usize) -> usize {
+        assert!(min <= max, "empty range {min}..={max}");
+        min + (self.next_u64() % (max - min + 1) as u64) as usize
+    }
+
+    /// `true` with a probability of one in `n`.
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.range(1, n) == 1
+    }
+
+    pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() - 1)]
+    }
+}
+
+/// Runs `property` against `cases` generated inputs. Every case has its own seed, a failing case
+/// is reported with its seed and input and is the first case checked when `AOC_PROPERTY_SEED` is
+/// set to that seed.
+///
+/// # Panics
+///
+/// Panics if the property does not hold for a generated input.
+pub fn check<G, P>(cases: usize, generate: G, property: P)
+where
+    G: Fn(&mut Rng) -> String,
+    P: Fn(&str) + RefUnwindSafe,
+{
+    let first_seed = env::var("AOC_PROPERTY_SEED")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+
+    for seed in (first_seed..).take(cases) {
+        let input = generate(&mut Rng::new(seed));
+
+        if let Err(payload) = panic::catch_unwind(|| property(&input)) {
+            let message = payload
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| payload.downcast_ref::<&str>().copied())
+                .unwrap_or("the property panicked");
+
+            panic!("{message}\n\nfailing case (AOC_PROPERTY_SEED={seed}):\n{input}");
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, Rng};
+
+    #[test]
+    fn generates_reproducible_numbers() {
+        let numbers: Vec<usize> = (0..100).map(|_| Rng::new(7).range(3, 5)).collect();
+        assert!(numbers.iter().all(|x| *x == numbers[0]));
+
+        let mut rng = Rng::new(7);
+        let numbers: Vec<usize> = (0..100).map(|_| rng.range(3, 5)).collect();
+        assert!(numbers.iter().all(|x| (3..=5).contains(x)));
+        assert!((3..=5).all(|x| numbers.contains(&x)));
+    }
+
+    #[test]
+    fn reports_failing_cases() {
+        let result = std::panic::catch_unwind(|| {
+            check(
+                100,
+                |rng| rng.range(0, 9).to_string(),
+                |input| assert_ne!(input, "5"),
+            );
+        });
+
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        assert!(message.contains("failing case (AOC_PROPERTY_SEED="));
+        assert!(message.ends_with(":\n5"));
+    }
+}
@@ -0,0 +1,188 @@
+/// Module that compares the variants of a day, see [`registry::variants`](crate::template::registry::variants).
+/// Variants are compared by their [`PartReport`]s, so `solve` can compare variants that ran as separate
+/// binaries in the same way as `all` compares the variants it ran in-process.
+use std::{fmt, time::Duration};
+
+use crate::template::report::{PartReport, Status};
+
+/// The reports of the parts of one variant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantRun {
+    pub name: String,
+    pub parts: Vec<PartReport>,
+}
+
+/// A part that a variant answers differently than the main solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    pub variant: String,
+    pub part: u8,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let answer = |x: &Option<String>| x.clone().unwrap_or_else(|| "no answer".into());
+        write!(
+            f,
+            "{} answers part {} with {} instead of {}",
+            self.variant,
+            self.part,
+            answer(&self.actual),
+            answer(&self.expected)
+        )
+    }
+}
+
+/// The parts where a variant answers differently than the first run, i.e. the main solution.
+#[must_use]
+pub fn disagreements(runs: &[VariantRun]) -> Vec<Disagreement> {
+    let Some((main, variants)) = runs.split_first() else {
+        return vec![];
+    };
+
+    variants
+        .iter()
+        .flat_map(|variant| {
+            main.parts
+                .iter()
+                .zip(&variant.parts)
+                .filter(|(expected, actual)| expected.answer != actual.answer)
+                .map(|(expected, actual)| Disagreement {
+                    variant: variant.name.clone(),
+                    part: expected.part,
+                    expected: expected.answer.clone(),
+                    actual: actual.answer.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Renders the answers and median times of every variant side by side. Times of other variants are
+/// also given relative to the main solution.
+#[must_use]
+pub fn render(runs: &[VariantRun]) -> String {
+    let Some(main) = runs.first() else {
+        return String::new();
+    };
+
+    let mut rows = vec![std::iter::once("Variant".to_string())
+        .chain(main.parts.iter().map(|x| format!("Part {}", x.part)))
+        .collect::<Vec<_>>()];
+
+    for (index, run) in runs.iter().enumerate() {
+        let mut row = vec![run.name.clone()];
+        for (report, main) in run.parts.iter().zip(&main.parts) {
+            row.push(cell(report, (index > 0).then_some(main)));
+        }
+        rows.push(row);
+    }
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|x| x[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn cell(report: &PartReport, main: Option<&PartReport>) -> String {
+    match report.status {
+        Status::Solved => {
+            let answer = report.answer.as_deref().unwrap_or_default();
+            // multi-line answers, e.g. letters drawn in a grid, are shortened to their first line.
+            let answer = match answer.split_once('\n') {
+                Some((first, _)) => format!("{first}…"),
+                None => answer.to_string(),
+            };
+
+            let time = format!("{:.1?}", Duration::from_nanos(report.nanos));
+            match main.filter(|x| x.status == Status::Solved && x.nanos > 0) {
+                Some(main) => format!(
+                    "{answer} ({time}, {:.2}x)",
+                    report.nanos as f64 / main.nanos as f64
+                ),
+                None => format!("{answer} ({time})"),
+            }
+        }
+        Status::None => "✖".into(),
+        Status::Error => "✖ error".into(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{disagreements, render, VariantRun};
+    use crate::template::report::PartReport;
+    use crate::template::runner::{BenchStats, PartRun};
+    use crate::{day, year};
+
+    fn run(name: &str, answers: [Option<&str>; 2], micros: u64) -> VariantRun {
+        let report = |part: u8, answer: Option<&str>| {
+            let run = PartRun {
+                answer: answer.map(Into::into),
+                error: None,
+                stats: BenchStats::single(Duration::from_micros(micros)),
+                memory: None,
+            };
+            PartReport::new(year!(2023), day!(10), part, &run)
+        };
+
+        VariantRun {
+            name: name.into(),
+            parts: vec![report(1, answers[0]), report(2, answers[1])],
+        }
+    }
+
+    #[test]
+    fn finds_disagreements() {
+        let runs = [
+            run("main", [Some("8"), Some("4")], 100),
+            run("naive", [Some("8"), Some("4")], 300),
+            run("softwaregore", [Some("8"), None], 200),
+        ];
+
+        let disagreements = disagreements(&runs);
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(
+            disagreements[0].to_string(),
+            "softwaregore answers part 2 with no answer instead of 4"
+        );
+    }
+
+    #[test]
+    fn renders_variants_side_by_side() {
+        let runs = [
+            run("main", [Some("8"), None], 100),
+            run("softwaregore", [Some("8"), Some("4")], 250),
+        ];
+
+        assert_eq!(
+            render(&runs),
+            [
+                "Variant       Part 1              Part 2",
+                "main          8 (100.0µs)         ✖",
+                "softwaregore  8 (250.0µs, 2.50x)  4 (250.0µs)",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The year of an Advent of Code event (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a valid event year,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The default year of this repository, configured via the `AOC_YEAR` environment variable.
+    /// Falls back to the latest event if it is not set.
+    ///
+    /// Data and solutions of this year use the flat `data/<folder>` and `src/bin/DD.rs` layout,
+    /// other years are scoped to `data/<year>/<folder>` and `src/bin/<year>-DD.rs`.
+    pub fn from_env() -> Self {
+        std::env::var("AOC_YEAR")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_else(Self::latest)
+    }
+
+    /// The year of the most recent event, which starts on the 1st of December.
+    pub fn latest() -> Self {
+        let days_since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs() / 86400)
+            .unwrap_or_default();
+
+        // civil-from-days, see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        #[allow(clippy::cast_possible_wrap)]
+        let z = days_since_epoch as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = year as u16;
+
+        if month == 12 {
+            Self(year)
+        } else {
+            Self(year - 1)
+        }
+    }
+
+    /// Whether this is the year configured via `AOC_YEAR`, which uses the flat layout.
+    pub fn is_default(self) -> bool {
+        self == Self::from_env()
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an event year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting an event year of 2015 or later"
+            ),
+        );
+        $crate::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn latest_year_is_an_event() {
+        assert!(Year::latest().into_inner() >= 2023);
+    }
+}
+
+/* -------------------------------------------------------------------------- */